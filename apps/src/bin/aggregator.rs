@@ -0,0 +1,105 @@
+// This application aggregates `lsag_verifier` receipts proven independently into a
+// single Groth16 proof, so that one on-chain call to `verifyAggregatedRs` covers the
+// whole batch of ring signatures.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use alloy_primitives::hex;
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use methods::{LSAG_AGGREGATOR_ELF, LSAG_AGGREGATOR_ID, LSAG_VERIFIER_ID};
+use risc0_ethereum_contracts::encode_seal;
+use risc0_zkvm::{
+    default_prover, sha::Digest, ExecutorEnv, InnerReceipt, ProverOpts, Receipt, VerifierContext,
+};
+use serde::Serialize;
+
+/// Arguments of the aggregator CLI.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Bincode-serialized composite or succinct receipts of the lsag_verifier guest
+    #[clap(required = true)]
+    receipts: Vec<PathBuf>,
+
+    /// Where to write the bincode-serialized aggregated receipt
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+/// The arguments of `verifyAggregatedRs`, and the aggregated digests.
+#[derive(Serialize, Debug)]
+struct AggregatedProof {
+    seal: String,
+    journal: String,
+    digests: Vec<String>,
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let args = Args::parse();
+
+    let mut receipts = Vec::with_capacity(args.receipts.len());
+    let mut journals = Vec::with_capacity(args.receipts.len());
+    for path in &args.receipts {
+        let receipt = load_receipt(path).with_context(|| format!("receipt {}", path.display()))?;
+        let journal: [u8; 32] = receipt
+            .journal
+            .bytes
+            .as_slice()
+            .try_into()
+            .with_context(|| format!("unexpected journal length in {}", path.display()))?;
+        log::debug!("{} proves {}", path.display(), hex::encode(journal));
+        journals.push(journal);
+        receipts.push(receipt);
+    }
+
+    let mut builder = ExecutorEnv::builder();
+    for receipt in receipts {
+        builder.add_assumption(receipt);
+    }
+    let env = builder
+        .write(&(Digest::from(LSAG_VERIFIER_ID), &journals))?
+        .build()?;
+
+    let receipt = default_prover()
+        .prove_with_ctx(
+            env,
+            &VerifierContext::default(),
+            LSAG_AGGREGATOR_ELF,
+            &ProverOpts::groth16(),
+        )?
+        .receipt;
+    receipt
+        .verify(LSAG_AGGREGATOR_ID)
+        .context("aggregated receipt verification failed")?;
+
+    if let Some(path) = &args.output {
+        fs::write(path, bincode::serialize(&receipt)?)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+
+    let output = AggregatedProof {
+        seal: hex::encode_prefixed(encode_seal(&receipt)?),
+        journal: hex::encode_prefixed(&receipt.journal.bytes),
+        digests: journals.iter().map(hex::encode_prefixed).collect(),
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// Loads a receipt of the lsag_verifier guest that can be used as an assumption.
+fn load_receipt(path: &Path) -> Result<Receipt> {
+    let bytes = fs::read(path).context("failed to read the receipt")?;
+    let receipt: Receipt = bincode::deserialize(&bytes).context("failed to decode the receipt")?;
+    if let InnerReceipt::Groth16(_) = receipt.inner {
+        bail!("Groth16 receipts cannot be aggregated, prove a composite or succinct receipt");
+    }
+    receipt
+        .verify(LSAG_VERIFIER_ID)
+        .context("not a valid receipt of the lsag_verifier guest")?;
+    Ok(receipt)
+}
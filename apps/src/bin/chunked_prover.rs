@@ -0,0 +1,130 @@
+// This application proves a ring signature over a ring too large for a single guest run.
+// The challenge chain is proven chunk by chunk with the `lsag_chunk` guest, each chunk
+// starting from the challenge reached by the previous one, then the `lsag_chunked_verifier`
+// guest composes the chunk receipts into a single Groth16 proof for the `verifyChunked*Rs`
+// function of the kind of message.
+
+use std::{fs, path::PathBuf};
+
+use alloy_primitives::hex;
+use anyhow::{Context, Result};
+use apps::ring_signature::RingSignatureData;
+use clap::Parser;
+use methods::{LSAG_CHUNKED_VERIFIER_ELF, LSAG_CHUNKED_VERIFIER_ID, LSAG_CHUNK_ELF, LSAG_CHUNK_ID};
+use risc0_ethereum_contracts::encode_seal;
+use risc0_zkvm::{default_prover, sha::Digest, ExecutorEnv, ProverOpts, VerifierContext};
+use serde::{Deserialize, Serialize};
+
+/// Arguments of the chunked prover CLI.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// File holding the base64-encoded ring signature
+    #[clap(long)]
+    input: PathBuf,
+
+    /// Number of ring members verified by each chunk
+    #[clap(long, default_value_t = 256)]
+    chunk_size: u32,
+
+    /// Where to write the bincode-serialized receipt of the chunked verifier
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+/// The journal of the `lsag_chunk` guest, mirroring `ChunkJournal` in the guest crate.
+#[derive(Serialize, Deserialize, Debug)]
+struct ChunkJournal {
+    ring_commitment: [u8; 32],
+    context: [u8; 32],
+    ring_size: u32,
+    start: u32,
+    end: u32,
+    c_start: [u8; 32],
+    c_end: [u8; 32],
+}
+
+/// The arguments of the `verifyChunked*Rs` function, besides the ring signature data.
+#[derive(Serialize, Debug)]
+struct ChunkedProof {
+    function: &'static str,
+    seal: String,
+    journal: String,
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let args = Args::parse();
+
+    let input = fs::read_to_string(&args.input)
+        .with_context(|| format!("failed to read {}", args.input.display()))?
+        .trim()
+        .to_string();
+
+    let prover = default_prover();
+    let mut chunks: Vec<ChunkJournal> = Vec::new();
+    let mut receipts = Vec::new();
+    loop {
+        let previous = chunks.last();
+        let start = previous.map_or(0, |chunk| chunk.end);
+        let c_start = previous.map(|chunk| chunk.c_end);
+        let env = ExecutorEnv::builder()
+            .write(&(&input, start, args.chunk_size, c_start))?
+            .build()?;
+        // succinct receipts, to be resolved as assumptions of the chunked verifier
+        let receipt = prover
+            .prove_with_ctx(
+                env,
+                &VerifierContext::default(),
+                LSAG_CHUNK_ELF,
+                &ProverOpts::succinct(),
+            )
+            .with_context(|| format!("failed to prove the chunk starting at {}", start))?
+            .receipt;
+        let chunk: ChunkJournal = receipt.journal.decode()?;
+        log::info!(
+            "proved ring members {}..{} of {}",
+            chunk.start,
+            chunk.end,
+            chunk.ring_size
+        );
+        let done = chunk.end == chunk.ring_size;
+        chunks.push(chunk);
+        receipts.push(receipt);
+        if done {
+            break;
+        }
+    }
+
+    let mut builder = ExecutorEnv::builder();
+    for receipt in receipts {
+        builder.add_assumption(receipt);
+    }
+    let env = builder
+        .write(&(Digest::from(LSAG_CHUNK_ID), &input, &chunks))?
+        .build()?;
+    let receipt = prover
+        .prove_with_ctx(
+            env,
+            &VerifierContext::default(),
+            LSAG_CHUNKED_VERIFIER_ELF,
+            &ProverOpts::groth16(),
+        )?
+        .receipt;
+    receipt
+        .verify(LSAG_CHUNKED_VERIFIER_ID)
+        .context("chunked verifier receipt verification failed")?;
+
+    if let Some(path) = &args.output {
+        fs::write(path, bincode::serialize(&receipt)?)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+
+    let output = ChunkedProof {
+        function: RingSignatureData::from_b64_lsag(&input)?.chunked_function_name(),
+        seal: hex::encode_prefixed(encode_seal(&receipt)?),
+        journal: hex::encode_prefixed(&receipt.journal.bytes),
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// This application proves that a ring member endorsed the output of another RISC Zero
+// computation. The ring signature must sign the sha256 digest of the endorsed receipt's
+// journal, and the `lsag_endorsement` guest verifies the endorsed receipt as an assumption.
+
+use std::{fs, path::PathBuf};
+
+use alloy_primitives::{hex, B256};
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use methods::{LSAG_ENDORSEMENT_ELF, LSAG_ENDORSEMENT_ID};
+use risc0_ethereum_contracts::encode_seal;
+use risc0_zkvm::{
+    default_prover, sha::Digest, ExecutorEnv, InnerReceipt, ProverOpts, Receipt, VerifierContext,
+};
+use serde::Serialize;
+
+/// Arguments of the endorser CLI.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// File holding the base64-encoded ring signature over the endorsed journal digest
+    #[clap(long)]
+    input: PathBuf,
+
+    /// Bincode-serialized composite or succinct receipt of the endorsed computation
+    #[clap(long)]
+    endorsed_receipt: PathBuf,
+
+    /// Image ID of the endorsed computation
+    #[clap(long)]
+    endorsed_image_id: B256,
+
+    /// Where to write the bincode-serialized receipt of the endorsement
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+/// The arguments of `verifyEndorsementRs`, besides the ring signature data.
+#[derive(Serialize, Debug)]
+struct EndorsementProof {
+    seal: String,
+    journal: String,
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let args = Args::parse();
+
+    let input = fs::read_to_string(&args.input)
+        .with_context(|| format!("failed to read {}", args.input.display()))?
+        .trim()
+        .to_string();
+    let bytes = fs::read(&args.endorsed_receipt)
+        .with_context(|| format!("failed to read {}", args.endorsed_receipt.display()))?;
+    let endorsed: Receipt =
+        bincode::deserialize(&bytes).context("failed to decode the endorsed receipt")?;
+    if let InnerReceipt::Groth16(_) = endorsed.inner {
+        bail!("Groth16 receipts cannot be endorsed, prove a composite or succinct receipt");
+    }
+    let endorsed_image_id = Digest::from(args.endorsed_image_id.0);
+    endorsed
+        .verify(endorsed_image_id)
+        .context("the endorsed receipt does not match the endorsed image ID")?;
+    let endorsed_journal = endorsed.journal.bytes.clone();
+
+    let env = ExecutorEnv::builder()
+        .add_assumption(endorsed)
+        .write(&(endorsed_image_id, &endorsed_journal, &input))?
+        .build()?;
+    let receipt = default_prover()
+        .prove_with_ctx(
+            env,
+            &VerifierContext::default(),
+            LSAG_ENDORSEMENT_ELF,
+            &ProverOpts::groth16(),
+        )?
+        .receipt;
+    receipt
+        .verify(LSAG_ENDORSEMENT_ID)
+        .context("endorsement receipt verification failed")?;
+
+    if let Some(path) = &args.output {
+        fs::write(path, bincode::serialize(&receipt)?)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+
+    let output = EndorsementProof {
+        seal: hex::encode_prefixed(encode_seal(&receipt)?),
+        journal: hex::encode_prefixed(&receipt.journal.bytes),
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
@@ -16,156 +16,401 @@
 // to the Bonsai proving service and publish the received proofs directly
 // to your deployed app contract.
 
+use std::future::IntoFuture;
+use std::path::PathBuf;
+
 use alloy::{
-    network::EthereumWallet, providers::ProviderBuilder, signers::local::PrivateKeySigner,
+    network::{EthereumWallet, TransactionBuilder},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::TransactionRequest,
 };
-use alloy_primitives::{Address, U256};
-use anyhow::Result;
-use clap::Parser;
+use alloy_primitives::hex;
+use anyhow::{bail, Context, Result};
+use apps::export::{export, TransactionArgs};
+use apps::input::SignatureArgs;
+use apps::preflight::{preflight, Preflight, Stage};
+use apps::profile::NetworkArgs;
+use apps::proof::{receipt_kind_name, ReceiptKind, SavedProof};
+use apps::send::{send, SendArgs};
+use apps::wallet::{encrypt_keystore, read_private_key, PasswordArgs, WalletArgs};
+use clap::{Parser, Subcommand, ValueEnum};
 use methods::LSAG_VERIFIER_ELF;
-use risc0_ethereum_contracts::encode_seal;
-use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, VerifierContext};
-use url::Url;
-
-alloy::sol!(
-    #[sol(rpc, all_derives)]
-    "../contracts/ILsagVerifier.sol"
-);
+use risc0_zkvm::{default_executor, default_prover, is_dev_mode, ExecutorEnv, VerifierContext};
 
 /// Arguments of the publisher CLI.
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-    /// Ethereum chain ID
+    /// Produce fake receipts, verified by a mock verifier contract, instead of proving
+    #[clap(
+        long,
+        global = true,
+        env = "RISC0_DEV_MODE",
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    dev_mode: bool,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the guest in the executor only, and report its cost without proving
+    DryRun(SignatureArgs),
+    /// Check the signature natively and in the executor, as done before proving
+    Preflight(SignatureArgs),
+    /// Prove the signature and save the receipt, seal and journal
+    Prove(ProveArgs),
+    /// Verify a saved receipt locally
+    Verify(ProofDirArgs),
+    /// Verify a saved receipt with the deployed contract
+    Submit(Box<SubmitArgs>),
+    /// Prove the signature, save the proof and verify it with the deployed contract
+    Publish(Box<PublishArgs>),
+    /// Export the calldata verifying a saved receipt, and optionally its unsigned transaction
+    Export(Box<ExportArgs>),
+    /// Encrypt an existing private key into a JSON keystore for submit and publish
+    CreateKeystore(CreateKeystoreArgs),
+}
+
+/// Where a proof is saved.
+#[derive(clap::Args, Debug)]
+struct ProofDirArgs {
+    /// Directory holding the receipt, the signature, and the seal and journal of the proof
+    #[clap(long, default_value = "proof")]
+    proof_dir: PathBuf,
+}
+
+/// Arguments of the prove subcommand.
+#[derive(clap::Args, Debug)]
+struct ProveArgs {
+    #[clap(flatten)]
+    proof_dir: ProofDirArgs,
+
+    /// Overwrite a receipt already saved in the proof directory
     #[clap(long)]
-    chain_id: u64,
+    force: bool,
+
+    /// Kind of receipt to prove. Only groth16 receipts can be submitted, outside dev mode
+    #[clap(long, value_enum, default_value = "groth16")]
+    receipt_kind: ReceiptKind,
+
+    #[clap(flatten)]
+    signature: SignatureArgs,
+}
+
+/// The deployed contract and the account calling it.
+#[derive(clap::Args, Debug)]
+struct ChainArgs {
+    #[clap(flatten)]
+    network: NetworkArgs,
 
-    /// Ethereum Node endpoint.
-    #[clap(long, env)]
-    eth_wallet_private_key: PrivateKeySigner,
+    #[clap(flatten)]
+    wallet: WalletArgs,
+
+    #[clap(flatten)]
+    send: SendArgs,
+}
 
-    /// Ethereum Node endpoint.
+/// Arguments of the submit subcommand.
+#[derive(clap::Args, Debug)]
+struct SubmitArgs {
+    #[clap(flatten)]
+    chain: ChainArgs,
+
+    #[clap(flatten)]
+    proof_dir: ProofDirArgs,
+}
+
+/// Arguments of the publish subcommand.
+#[derive(clap::Args, Debug)]
+struct PublishArgs {
+    #[clap(flatten)]
+    chain: ChainArgs,
+
+    #[clap(flatten)]
+    prove: ProveArgs,
+}
+
+/// Arguments of the export subcommand.
+#[derive(clap::Args, Debug)]
+struct ExportArgs {
+    #[clap(flatten)]
+    network: NetworkArgs,
+
+    #[clap(flatten)]
+    proof_dir: ProofDirArgs,
+
+    #[clap(flatten)]
+    transaction: TransactionArgs,
+
+    /// File to write the JSON export to, instead of stdout
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+/// Arguments of the create-keystore subcommand.
+#[derive(clap::Args, Debug)]
+struct CreateKeystoreArgs {
+    /// Path of the keystore to create
     #[clap(long)]
-    rpc_url: Url,
+    keystore: PathBuf,
 
-    /// Application's contract address on Ethereum
+    /// File holding the hex private key to encrypt. Without it, or with `-`, it is read from stdin
     #[clap(long)]
-    contract: Address,
+    private_key_file: Option<PathBuf>,
+
+    /// Overwrite an existing keystore
+    #[clap(long)]
+    force: bool,
+
+    #[clap(flatten)]
+    password: PasswordArgs,
 }
 
 fn main() -> Result<()> {
     env_logger::init();
     // Parse CLI Arguments: The application starts by parsing command-line arguments provided by the user.
     let args = Args::parse();
+    if args.dev_mode {
+        // the prover and the receipt verification check the environment
+        std::env::set_var("RISC0_DEV_MODE", "1");
+    }
 
-    // Create an alloy provider for that private key and URL.
-    let wallet = EthereumWallet::from(args.eth_wallet_private_key);
-    let provider = ProviderBuilder::new()
-        .with_recommended_fillers()
-        .wallet(wallet)
-        .on_http(args.rpc_url);
+    match args.command {
+        Command::DryRun(signature) => dry_run(&signature.read()?),
+        Command::Preflight(signature) => run_preflight(&signature.read()?).map(|_| ()),
+        Command::Prove(args) => prove(&args).map(|_| ()),
+        Command::Verify(args) => verify(&SavedProof::load(&args.proof_dir)?),
+        Command::Submit(args) => submit(&args.chain, &SavedProof::load(&args.proof_dir.proof_dir)?),
+        Command::Publish(args) => {
+            // only Groth16 and dev-mode receipts have a seal the contract verifies
+            if args.prove.receipt_kind != ReceiptKind::Groth16 && !is_dev_mode() {
+                bail!(
+                    "a {} receipt cannot be verified on chain, publish with --receipt-kind groth16",
+                    args.prove
+                        .receipt_kind
+                        .to_possible_value()
+                        .unwrap()
+                        .get_name()
+                );
+            }
+            // a missing network setting must not be found only once the proof is done
+            args.chain.network.resolve()?;
+            // neither must a wrong keystore password
+            args.chain.wallet.signer()?;
+            submit(&args.chain, &prove(&args.prove)?)
+        }
+        Command::Export(args) => export_call(&args),
+        Command::CreateKeystore(args) => create_keystore(&args),
+    }
+}
+
+/// Executes the guest without proving, and reports its cycles, segments, journal and the
+/// cycles of each verification phase. It needs no network access.
+fn dry_run(input: &str) -> Result<()> {
+    let mut guest_stdout = Vec::new();
+    let env = ExecutorEnv::builder()
+        .env_var("LSAG_CYCLES", "1")
+        .stdout(&mut guest_stdout)
+        .write(&input)?
+        .build()?;
+    let session = default_executor()
+        .execute(env, LSAG_VERIFIER_ELF)
+        .context("guest execution failed")?;
+
+    let padded_cycles: u64 = session.segments.iter().map(|s| 1u64 << s.po2).sum();
+    println!("Exit code: {:?}", session.exit_code);
+    println!("User cycles: {}", session.cycles());
+    println!("Total cycles: {} (padded to segment sizes)", padded_cycles);
+    println!("Segments: {}", session.segments.len());
+    println!("Journal: {}", hex::encode_prefixed(&session.journal.bytes));
+    println!("Cycles per phase:");
+    for line in String::from_utf8_lossy(&guest_stdout).lines() {
+        if let Some(phase) = line.strip_prefix("lsag-cycles ") {
+            let (name, cycles) = phase.split_once(' ').unwrap_or((phase, ""));
+            println!("  {}: {}", name, cycles);
+        }
+    }
+    Ok(())
+}
 
-    let input = "eyJtZXNzYWdlIjoiSGVsbG8gV29ybGQiLCJyaW5nIjpbIjAyMjE4NjljYTNhZTMzYmUzYTczMjdlOWEwMjcyMjAzYWZhNzJjNTJhNTQ2MGNlYjlmNGE1MDkzMDUzMWJkOTI2YSIsIjAzNDI4ZTAyMGYxODRiNzBjYTkzMWE5MTA4NWFjMWMyMzM4MjdhNDFkODUxNmE0YjY0NTVlMjIxZTYzN2M0ZGUwZiIsIjAzNDM4ZmJmMzc3NmNjMjRlMjUzNTgyMjU0NGYxNGQ3YjA1N2Q4OTU3YzcyNjc2MDE4MDA1MmNkYTdiOGJhNmM4MyIsIjAzNTBjMWJkNjRjMzA4N2Y2NWY0ODE3MTdlZTRhNWJkZmJiYTRmMDYwMzE0OTkzZjFlMTVjMGRiMjk3NDhiOGRjMiJdLCJjIjoiM2M3ZDBhYzE4YjBlYWU4N2M1OTFjMGM5ZWRkOWE3ZDU3YjI5ZWUxZDhiNzZlYTFjOGM1NjAxMDQ3MGMwZDViMiIsInJlc3BvbnNlcyI6WyIxOWUzNGNjOTc5Y2E1YWMzYTk2MThkMGNlZThmYjdmNzRlMmY4MzA5MmY2ZDZmOTUyZTA3OWYxMzY1MmNlNjM2IiwiYjRkZGE5ZTc4YzA4OTliYjFjYmNkYTVjMjhiYWRiZjYwYWIzMDc1N2MyZjVhMWIxNWQwZDliNmQ1MzdhMTMwMSIsIjJhY2Q4ZWIzMzZhZjU5YzIwMTVhNDljMGJlMWZhZmE3Yzk0ODRmYWQ4YmY3MmFmYjZjYmIwYzgzMDhhOGUxODUiLCI1Y2IzNWY3OWVmYzBmODEwYTI0NTMxYjU0YWM0NThiNjZkMTZlNzNhMTdjOWEyY2IxYTkyN2QzYzI1YTNkMDY4Il0sImN1cnZlIjoie1wiY3VydmVcIjpcIlNFQ1AyNTZLMVwifSIsImtleUltYWdlIjoiMDJlN2ZmMzQ5MGVlN2RiMzM3NTBmZTlhNzA5MWQ4MmRjYTk1MmU2ZDIyYTdmZDRkZjk3ZDBjYmY4ZjdjYjQ2YWQyIiwibGlua2FiaWxpdHlGbGFnIjoibGlua2FiaWxpdHkiLCJjb25maWciOnsiaGFzaCI6InNoYTI1NiJ9fQ==";
+/// Runs the preflight checks and reports each stage.
+fn run_preflight(input: &str) -> Result<Preflight> {
+    let preflight = preflight(input)?;
+    println!("Preflight passed:");
+    println!("  {}: ok", Stage::Parsing);
+    println!("  {}: ok", Stage::NativeVerification);
+    println!(
+        "  {}: ok, {} cycles",
+        Stage::GuestExecution,
+        preflight.cycles
+    );
+    println!(
+        "  {}: ok, {} for {}",
+        Stage::AbiDigest,
+        hex::encode_prefixed(preflight.journal),
+        preflight.ring_signature.function_name()
+    );
+    Ok(preflight)
+}
+
+/// Proves the signature and saves the proof, so that it survives a failed submission.
+fn prove(args: &ProveArgs) -> Result<SavedProof> {
+    let input = args.signature.read()?;
+    run_preflight(&input)?;
 
-    let env = ExecutorEnv::builder().write(&input).unwrap().build()?;
+    let mut builder = ExecutorEnv::builder();
+    // In dev mode, LSAG_TRACE makes the guest print the trace of the challenge chain
+    if is_dev_mode() && std::env::var("LSAG_TRACE").is_ok() {
+        builder.env_var("LSAG_TRACE", "1");
+    }
+    let env = builder.write(&input)?.build()?;
 
+    if is_dev_mode() {
+        println!("Dev mode: the receipt is fake, it only verifies with a mock verifier");
+    }
     let receipt = default_prover()
         .prove_with_ctx(
             env,
             &VerifierContext::default(),
             LSAG_VERIFIER_ELF,
-            &ProverOpts::groth16(),
+            &args.receipt_kind.prover_opts(),
         )?
         .receipt;
 
-    let seal = encode_seal(&receipt)?;
-    let journal = receipt.journal.bytes.clone();
-    let ring_signature = ILsagVerifier::RingSignatureData {
-        message: "Hello World".to_string(),
-        linkabilityFlag: "linkability".to_string(),
-        ring: vec![
-            ILsagVerifier::Point {
-                x: U256::from_str_radix(
-                    "21869ca3ae33be3a7327e9a0272203afa72c52a5460ceb9f4a50930531bd926a",
-                    16,
-                )
-                .unwrap(),
-                y: U256::from_str_radix(
-                    "2c9535b5f71409cf334b639de2d39074ea35c3057857e32ca5487f5d3c68d982",
-                    16,
-                )
-                .unwrap(),
-            },
-            ILsagVerifier::Point {
-                x: U256::from_str_radix(
-                    "428e020f184b70ca931a91085ac1c233827a41d8516a4b6455e221e637c4de0f",
-                    16,
-                )
-                .unwrap(),
-                y: U256::from_str_radix(
-                    "732a3d83f31b7fa512cda0ccd72e747559066d005f9f9d1660f238d1b346ae85",
-                    16,
-                )
-                .unwrap(),
-            },
-            ILsagVerifier::Point {
-                x: U256::from_str_radix(
-                    "438fbf3776cc24e2535822544f14d7b057d8957c726760180052cda7b8ba6c83",
-                    16,
-                )
-                .unwrap(),
-                y: U256::from_str_radix(
-                    "678be57a16f69979248530cc00c357af31e2c89e62c18a2f562174b9976d6e5f",
-                    16,
-                )
-                .unwrap(),
-            },
-            ILsagVerifier::Point {
-                x: U256::from_str_radix(
-                    "50c1bd64c3087f65f481717ee4a5bdfbba4f060314993f1e15c0db29748b8dc2",
-                    16,
-                )
-                .unwrap(),
-                y: U256::from_str_radix(
-                    "21300f258285169c801c3a977fbb303ccac4a0a8513f166878bd229f8c9b658f",
-                    16,
-                )
-                .unwrap(),
-            },
-        ],
-        keyImage: ILsagVerifier::Point {
-            x: U256::from_str_radix(
-                "e7ff3490ee7db33750fe9a7091d82dca952e6d22a7fd4df97d0cbf8f7cb46ad2",
-                16,
-            )
-            .unwrap(),
-            y: U256::from_str_radix(
-                "027c6ce97078f8bd89ed755f7bc42a32d73de9826a9c4eb1db51344cd0839c30",
-                16,
-            )
-            .unwrap(),
-        },
+    let proof = SavedProof {
+        receipt,
+        signature: input,
     };
-    let contract = ILsagVerifier::new(args.contract, provider);
-    let call_builder = contract.verifyRs(
-        seal.clone().into(),
-        journal.clone().into(),
-        ring_signature.clone(),
+    let directory = &args.proof_dir.proof_dir;
+    proof.save(directory, args.force)?;
+    println!(
+        "{} proof saved to {}",
+        receipt_kind_name(&proof.receipt),
+        directory.display()
     );
+    Ok(proof)
+}
+
+/// Checks a saved proof locally, as `submit` does before calling the contract.
+fn verify(proof: &SavedProof) -> Result<()> {
+    let ring_signature = proof.verify()?;
+    println!("Receipt verified ({})", receipt_kind_name(&proof.receipt));
+    println!(
+        "Journal: {}",
+        hex::encode_prefixed(&proof.receipt.journal.bytes)
+    );
+    println!("Contract function: {}", ring_signature.function_name());
+    Ok(())
+}
+
+/// Calls the function of the deployed contract verifying the kind of message of a saved proof,
+/// with the ring signature data decoded from its signature. The proof is verified locally
+/// first, so that a proof the contract would reject is never sent. With `--send`, the call is
+/// then sent as a transaction, once the eth_call showed it succeeds.
+fn submit(args: &ChainArgs, proof: &SavedProof) -> Result<()> {
+    let network = args.network.resolve()?;
+    let ring_signature = proof
+        .verify()
+        .context("local verification of the proof failed")?;
+    let calldata = ring_signature.calldata(
+        proof.seal()?.into(),
+        proof.receipt.journal.bytes.clone().into(),
+    );
+
+    let signer = args.wallet.signer()?;
+    let request = TransactionRequest::default()
+        .with_from(signer.address())
+        .with_to(network.contract)
+        .with_input(calldata);
+    let wallet = EthereumWallet::from(signer);
+    let provider = ProviderBuilder::new().on_http(network.rpc_url);
+    let policy = args.send.retry_policy();
 
     //set up async runtime with tokio
     let runtime = tokio::runtime::Runtime::new()?;
     runtime.block_on(async {
-        match call_builder.call().await {
-            Ok(value) => {
-                println!("Raw return value: {:?}", value);
-                Ok(value)
-            }
+        let chain_id = policy
+            .rpc("chain ID query", || provider.get_chain_id())
+            .await?;
+        if chain_id != network.chain_id {
+            bail!(
+                "the RPC endpoint serves chain {}, not chain {}",
+                chain_id,
+                network.chain_id
+            );
+        }
+        println!(
+            "Calling {} on {} (chain {})",
+            ring_signature.function_name(),
+            network.contract,
+            chain_id
+        );
+
+        let output = match policy
+            .rpc("eth_call", || provider.call(&request).into_future())
+            .await
+        {
+            Ok(output) => output,
             Err(e) => {
                 println!("Error details: {:?}", e);
-                Err(e)
+                return Err(e.into());
             }
+        };
+        println!(
+            "Raw return value: {}",
+            ring_signature.decode_return(&output)?
+        );
+        if !args.send.send {
+            return Ok(());
         }
-    })?;
+
+        let receipt = send(&provider, &wallet, request, chain_id, &args.send).await?;
+        println!(
+            "Transaction {} confirmed in block {} ({} confirmations), {} gas used",
+            receipt.transaction_hash,
+            receipt
+                .block_number
+                .map_or("unknown".to_string(), |block| block.to_string()),
+            args.send.confirmations,
+            receipt.gas_used
+        );
+        Ok(())
+    })
+}
+
+/// Exports the call verifying a saved proof as JSON, for accounts signing elsewhere. It needs
+/// neither a key nor network access.
+fn export_call(args: &ExportArgs) -> Result<()> {
+    let deployment = args.network.resolve_deployment()?;
+    let proof = SavedProof::load(&args.proof_dir.proof_dir)?;
+    let json = serde_json::to_string_pretty(&export(
+        &proof,
+        deployment,
+        &args.transaction,
+        is_dev_mode(),
+    )?)?;
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, format!("{}\n", json))
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!("Export written to {}", path.display());
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Encrypts a private key into a keystore, so that it is never passed on the command line.
+fn create_keystore(args: &CreateKeystoreArgs) -> Result<()> {
+    let signer = read_private_key(args.private_key_file.as_deref())?;
+    encrypt_keystore(&signer, &args.keystore, &args.password.read()?, args.force)?;
+    println!(
+        "Keystore of {} written to {}",
+        signer.address(),
+        args.keystore.display()
+    );
     Ok(())
 }
@@ -0,0 +1,47 @@
+// This application recovers the secp256k1 public keys of Ethereum accounts from a
+// local dump of signed transactions and signed messages. The keys are printed in
+// the compressed hex format expected by `deserialize_ring`, keyed by address, so
+// rings can be assembled from a list of addresses.
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use apps::ring_keys::{parse_addresses, recover_keys, select_keys, SignedEntry};
+use clap::Parser;
+
+/// Arguments of the ring builder CLI.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// JSON file holding an array of signed transactions and signed messages
+    #[clap(long)]
+    input: PathBuf,
+
+    /// File listing the addresses to keep, one per line. Every address must be recovered
+    #[clap(long)]
+    addresses: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let args = Args::parse();
+
+    let dump = fs::read_to_string(&args.input)
+        .with_context(|| format!("failed to read {}", args.input.display()))?;
+    let entries: Vec<SignedEntry> =
+        serde_json::from_str(&dump).context("failed to parse the signed payload dump")?;
+    let mut keys = recover_keys(&entries)?;
+
+    if let Some(path) = args.addresses {
+        let list = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        keys = select_keys(keys, &parse_addresses(&list)?)?;
+    }
+
+    let output: BTreeMap<String, String> = keys
+        .into_iter()
+        .map(|(address, key)| (address.to_checksum(None), key))
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// This application proves the outcome of the verification of a ring signature, whether it
+// is valid or not. The `lsag_status` guest commits a status code instead of panicking, so
+// that `verifyStatus` can prove on chain that a published signature is invalid.
+
+use std::{fs, path::PathBuf};
+
+use alloy_primitives::hex;
+use anyhow::{Context, Result};
+use clap::Parser;
+use methods::{LSAG_STATUS_ELF, LSAG_STATUS_ID};
+use risc0_ethereum_contracts::encode_seal;
+use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, VerifierContext};
+use serde::Serialize;
+
+/// Arguments of the status prover CLI.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// File holding the base64-encoded ring signature
+    #[clap(long)]
+    input: PathBuf,
+}
+
+/// The arguments of `verifyStatus`, besides the signature, and the proven status.
+#[derive(Serialize, Debug)]
+struct StatusProof {
+    seal: String,
+    journal: String,
+    status: u8,
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let args = Args::parse();
+
+    let input = fs::read_to_string(&args.input)
+        .with_context(|| format!("failed to read {}", args.input.display()))?
+        .trim()
+        .to_string();
+
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let receipt = default_prover()
+        .prove_with_ctx(
+            env,
+            &VerifierContext::default(),
+            LSAG_STATUS_ELF,
+            &ProverOpts::groth16(),
+        )?
+        .receipt;
+    receipt
+        .verify(LSAG_STATUS_ID)
+        .context("status receipt verification failed")?;
+
+    // abi.encode(uint8 status, bytes32 inputDigest, bytes32 lsagDigest)
+    let status = receipt.journal.bytes[31];
+    let output = StatusProof {
+        seal: hex::encode_prefixed(encode_seal(&receipt)?),
+        journal: hex::encode_prefixed(&receipt.journal.bytes),
+        status,
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
@@ -0,0 +1,6 @@
+// Bindings of the LSAG verifier contract.
+
+alloy::sol!(
+    #[sol(rpc, all_derives)]
+    "../contracts/ILsagVerifier.sol"
+);
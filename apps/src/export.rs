@@ -0,0 +1,236 @@
+// Offline exports of the contract call verifying a saved proof, for accounts that do not sign
+// with a local key, such as a multisig. The export holds the calldata of the verification
+// function of the proof, and optionally the unsigned EIP-1559 transaction carrying it, RLP
+// encoded as signed, with its signing hash.
+
+use alloy::consensus::{SignableTransaction, TxEip1559};
+use alloy_primitives::{hex, Address, Bytes, TxKind, U256};
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::profile::Deployment;
+use crate::proof::SavedProof;
+
+/// The fields of the unsigned transaction that only the sender knows. Fees are in wei.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct TransactionArgs {
+    /// Nonce of the sending account, exports the unsigned EIP-1559 transaction along with the
+    /// calldata
+    #[clap(
+        long,
+        requires_all = ["gas_limit", "max_fee_per_gas", "max_priority_fee_per_gas"]
+    )]
+    pub nonce: Option<u64>,
+
+    /// Gas limit of the transaction
+    #[clap(long, requires = "nonce")]
+    pub gas_limit: Option<u64>,
+
+    /// Maximum fee per gas of the transaction, in wei
+    #[clap(long, requires = "nonce")]
+    pub max_fee_per_gas: Option<u128>,
+
+    /// Maximum priority fee per gas of the transaction, in wei
+    #[clap(long, requires = "nonce")]
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+impl TransactionArgs {
+    /// Builds the unsigned transaction calling `contract` with `calldata`, if a nonce is given.
+    fn transaction(&self, deployment: Deployment, calldata: &[u8]) -> Result<Option<TxEip1559>> {
+        let Some(nonce) = self.nonce else {
+            if self.gas_limit.is_some()
+                || self.max_fee_per_gas.is_some()
+                || self.max_priority_fee_per_gas.is_some()
+            {
+                bail!("the transaction fields require --nonce");
+            }
+            return Ok(None);
+        };
+        let (Some(gas_limit), Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) = (
+            self.gas_limit,
+            self.max_fee_per_gas,
+            self.max_priority_fee_per_gas,
+        ) else {
+            bail!(
+                "the transaction requires --gas-limit, --max-fee-per-gas and \
+                 --max-priority-fee-per-gas"
+            );
+        };
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            bail!(
+                "the max priority fee per gas {} exceeds the max fee per gas {}",
+                max_priority_fee_per_gas,
+                max_fee_per_gas
+            );
+        }
+        Ok(Some(TxEip1559 {
+            chain_id: deployment.chain_id,
+            nonce,
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            to: TxKind::Call(deployment.contract),
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input: Bytes::copy_from_slice(calldata),
+        }))
+    }
+}
+
+/// The call verifying a saved proof. Byte strings are hex encoded, and fees are decimal
+/// strings of wei, as they may not fit a JSON number.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Export {
+    /// The contract function called, as picked from the kind of message
+    pub function: &'static str,
+    pub chain_id: u64,
+    pub to: Address,
+    pub value: String,
+    pub data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<UnsignedTransaction>,
+}
+
+/// An unsigned EIP-1559 transaction.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransaction {
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+    /// `0x02 || rlp([chainId, nonce, ..., accessList])`, the payload to sign
+    pub unsigned: String,
+    /// The keccak256 hash of the payload, which the sender signs
+    pub signing_hash: String,
+}
+
+/// Exports the call verifying `proof` with the deployed contract. The proof is verified
+/// locally first, so that a call the contract would reject is never exported: fake receipts
+/// are only exported with `dev_mode`.
+pub fn export(
+    proof: &SavedProof,
+    deployment: Deployment,
+    transaction: &TransactionArgs,
+    dev_mode: bool,
+) -> Result<Export> {
+    let ring_signature = proof.verify_with(dev_mode)?;
+    let calldata = ring_signature.calldata(
+        proof.seal()?.into(),
+        proof.receipt.journal.bytes.clone().into(),
+    );
+    let transaction = transaction
+        .transaction(deployment, &calldata)?
+        .map(|transaction| UnsignedTransaction {
+            nonce: transaction.nonce,
+            gas_limit: transaction.gas_limit,
+            max_fee_per_gas: transaction.max_fee_per_gas.to_string(),
+            max_priority_fee_per_gas: transaction.max_priority_fee_per_gas.to_string(),
+            unsigned: hex::encode_prefixed(transaction.encoded_for_signing()),
+            signing_hash: transaction.signature_hash().to_string(),
+        });
+    Ok(Export {
+        function: ring_signature.function_name(),
+        chain_id: deployment.chain_id,
+        to: deployment.contract,
+        value: "0".to_string(),
+        data: hex::encode_prefixed(calldata),
+        transaction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::ILsagVerifier;
+    use crate::fixtures::demo_proof;
+    use crate::ring_signature::RingSignatureData;
+    use alloy::consensus::TxEnvelope;
+    use alloy::eips::eip2718::{Decodable2718, Encodable2718};
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::signers::SignerSync;
+    use alloy_primitives::keccak256;
+    use alloy_rlp::Header;
+    use alloy_sol_types::SolCall;
+
+    fn deployment() -> Deployment {
+        Deployment {
+            chain_id: 31337,
+            contract: Address::repeat_byte(1),
+        }
+    }
+
+    fn transaction_args() -> TransactionArgs {
+        TransactionArgs {
+            nonce: Some(7),
+            gas_limit: Some(2_000_000),
+            max_fee_per_gas: Some(30_000_000_000),
+            max_priority_fee_per_gas: Some(1_000_000_000),
+        }
+    }
+
+    #[test]
+    fn calldata_decodes_to_the_proof() {
+        let proof = demo_proof();
+        let export = export(&proof, deployment(), &TransactionArgs::default(), true).unwrap();
+        assert_eq!(export.function, "verifyRs");
+        assert!(export.transaction.is_none());
+
+        let call =
+            ILsagVerifier::verifyRsCall::abi_decode(&hex::decode(&export.data).unwrap(), true)
+                .unwrap();
+        assert_eq!(call.seal.to_vec(), proof.seal().unwrap());
+        assert_eq!(call.journal.to_vec(), proof.receipt.journal.bytes);
+        let RingSignatureData::Utf8(data) = proof.verify_with(true).unwrap() else {
+            panic!("the demo message is UTF-8");
+        };
+        assert_eq!(call._ringSignatureData, data);
+    }
+
+    #[test]
+    fn signed_transactions_carry_the_calldata() {
+        let export = export(&demo_proof(), deployment(), &transaction_args(), true).unwrap();
+        let transaction = export.transaction.unwrap();
+
+        // sign the hash as a multisig or a hardware wallet would, and rebuild the signed
+        // transaction from the unsigned one
+        let signer = PrivateKeySigner::random();
+        let hash = transaction.signing_hash.parse().unwrap();
+        let signature = signer.sign_hash_sync(&hash).unwrap();
+        let unsigned = hex::decode(&transaction.unsigned).unwrap();
+        assert_eq!(keccak256(&unsigned).to_string(), transaction.signing_hash);
+        let (tx_type, mut fields) = unsigned.split_first().unwrap();
+        assert_eq!(*tx_type, 2);
+        let header = Header::decode(&mut fields).unwrap();
+        assert!(header.list);
+        assert_eq!(header.payload_length, fields.len());
+        let unsigned = TxEip1559::decode_fields(&mut fields).unwrap();
+        assert!(fields.is_empty());
+        let signed = TxEnvelope::from(unsigned.into_signed(signature));
+        let mut encoded = Vec::new();
+        signed.encode_2718(&mut encoded);
+
+        let TxEnvelope::Eip1559(signed) = TxEnvelope::decode_2718(&mut encoded.as_slice()).unwrap()
+        else {
+            panic!("not an EIP-1559 transaction");
+        };
+        assert_eq!(signed.recover_signer().unwrap(), signer.address());
+        assert_eq!(signed.tx().input, hex::decode(&export.data).unwrap());
+        assert_eq!(signed.tx().to, TxKind::Call(Address::repeat_byte(1)));
+        assert_eq!(signed.tx().chain_id, 31337);
+        assert_eq!(signed.tx().nonce, 7);
+    }
+
+    #[test]
+    fn inconsistent_fees_are_rejected() {
+        let mut args = transaction_args();
+        args.max_priority_fee_per_gas = Some(40_000_000_000);
+        let error = export(&demo_proof(), deployment(), &args, true).unwrap_err();
+        assert!(error.to_string().contains("exceeds the max fee per gas"));
+        args.nonce = None;
+        let error = export(&demo_proof(), deployment(), &args, true).unwrap_err();
+        assert!(error.to_string().contains("require --nonce"));
+    }
+}
@@ -0,0 +1,31 @@
+// Fixtures shared by the unit tests of the crate.
+
+use methods::LSAG_VERIFIER_ID;
+use risc0_zkvm::sha::Digest;
+use risc0_zkvm::{FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+
+use crate::proof::SavedProof;
+use crate::ring_signature::RingSignatureData;
+
+/// The base64-encoded demo signature over "Hello World".
+const DEMO: &str = include_str!("../fixtures/demo_signature.b64");
+
+/// The demo signature, without the trailing newline of its file.
+pub fn demo() -> &'static str {
+    DEMO.trim()
+}
+
+/// A fake receipt of `image_id` committing `journal`, saved along with the demo signature.
+pub fn fake_proof(image_id: impl Into<Digest>, journal: Vec<u8>) -> SavedProof {
+    let claim = ReceiptClaim::ok(image_id, journal.clone());
+    SavedProof {
+        receipt: Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal),
+        signature: demo().to_string(),
+    }
+}
+
+/// The fake receipt the prover produces for the demo signature in dev mode.
+pub fn demo_proof() -> SavedProof {
+    let journal = RingSignatureData::from_b64_lsag(demo()).unwrap().digest();
+    fake_proof(LSAG_VERIFIER_ID, journal.to_vec())
+}
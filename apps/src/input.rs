@@ -0,0 +1,102 @@
+// Reading the ring signature given to an app: from an argument, a file or stdin, either base64
+// encoded as produced by the signing library or as raw JSON.
+
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose;
+use base64::Engine;
+use serde_json::Value;
+
+/// Where to read the ring signature from. Without `--signature` or `--signature-file`, it is
+/// read from stdin.
+#[derive(clap::Args, Debug, Clone)]
+pub struct SignatureArgs {
+    /// Ring signature, base64 encoded or as JSON
+    #[clap(long, conflicts_with = "signature_file")]
+    pub signature: Option<String>,
+
+    /// File holding the ring signature, base64 encoded or as JSON. `-` reads stdin
+    #[clap(long)]
+    pub signature_file: Option<PathBuf>,
+}
+
+impl SignatureArgs {
+    /// Reads the signature and returns it base64 encoded, as the guests expect it.
+    pub fn read(&self) -> Result<String> {
+        let raw = match (&self.signature, &self.signature_file) {
+            (Some(signature), _) => signature.clone(),
+            (None, Some(path)) if path.as_os_str() != "-" => fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?,
+            (None, _) => {
+                let mut stdin = io::stdin();
+                if self.signature_file.is_none() && stdin.is_terminal() {
+                    bail!("no signature given: pass --signature, --signature-file or stdin");
+                }
+                let mut raw = String::new();
+                stdin
+                    .read_to_string(&mut raw)
+                    .context("failed to read the signature from stdin")?;
+                raw
+            }
+        };
+        normalize_signature(&raw)
+    }
+}
+
+/// Returns the base64 encoding of a signature given either as JSON or base64 encoded. Base64
+/// input may be wrapped over several lines.
+pub fn normalize_signature(raw: &str) -> Result<String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        bail!("the signature is empty");
+    }
+    if raw.starts_with('{') {
+        serde_json::from_str::<Value>(raw).context("the signature is not valid JSON")?;
+        return Ok(general_purpose::STANDARD.encode(raw));
+    }
+
+    let b64: String = raw.chars().filter(|c| !c.is_whitespace()).collect();
+    let json = general_purpose::STANDARD
+        .decode(&b64)
+        .context("the signature is neither JSON nor base64")?;
+    serde_json::from_slice::<Value>(&json)
+        .context("the base64 signature does not decode to JSON")?;
+    Ok(b64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{"message":"Hello World","ring":[]}"#;
+
+    #[test]
+    fn json_signatures_are_base64_encoded() {
+        let b64 = normalize_signature(&format!("  {}\n", JSON)).unwrap();
+        assert_eq!(
+            general_purpose::STANDARD.decode(b64).unwrap(),
+            JSON.as_bytes()
+        );
+    }
+
+    #[test]
+    fn wrapped_base64_signatures_are_joined() {
+        let b64 = general_purpose::STANDARD.encode(JSON);
+        let (head, tail) = b64.split_at(16);
+        assert_eq!(
+            normalize_signature(&format!("{}\n{}\n", head, tail)).unwrap(),
+            b64
+        );
+    }
+
+    #[test]
+    fn invalid_signatures_are_rejected() {
+        assert!(normalize_signature("").is_err());
+        assert!(normalize_signature("{not json").is_err());
+        assert!(normalize_signature("not base64!").is_err());
+        assert!(normalize_signature(&general_purpose::STANDARD.encode("not json")).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+pub mod contract;
+pub mod export;
+#[cfg(test)]
+mod fixtures;
+pub mod input;
+pub mod preflight;
+pub mod profile;
+pub mod proof;
+pub mod ring_keys;
+pub mod ring_signature;
+pub mod send;
+pub mod wallet;
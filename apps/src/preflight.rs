@@ -0,0 +1,180 @@
+// Checks run on a signature before spending any proving time. Each stage only runs once the
+// previous ones pass, and a failure reports the stage it happened in.
+
+use std::fmt;
+
+use alloy_primitives::hex;
+use anyhow::{anyhow, bail, Context};
+use lsag_core::lsag_verifier::status::LsagStatus;
+use lsag_core::lsag_verifier::try_verify_b64_lsag;
+use methods::LSAG_VERIFIER_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use crate::ring_signature::RingSignatureData;
+
+/// The stages of the preflight checks, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// Decoding the signature and each of its fields
+    Parsing,
+    /// Verifying the signature natively
+    NativeVerification,
+    /// Executing the guest without proving
+    GuestExecution,
+    /// Comparing the journal with the digest of the ring signature data sent to the contract
+    AbiDigest,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Stage::Parsing => "parsing",
+            Stage::NativeVerification => "native verification",
+            Stage::GuestExecution => "guest execution",
+            Stage::AbiDigest => "ABI digest",
+        })
+    }
+}
+
+/// A failed preflight check.
+#[derive(Debug)]
+pub struct PreflightError {
+    pub stage: Stage,
+    pub error: anyhow::Error,
+}
+
+impl fmt::Display for PreflightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "preflight failed at {}: {:#}", self.stage, self.error)
+    }
+}
+
+impl std::error::Error for PreflightError {}
+
+/// What the preflight checks computed.
+#[derive(Debug)]
+pub struct Preflight {
+    /// The ring signature data sent to the contract
+    pub ring_signature: RingSignatureData,
+    /// The journal committed by the guest, `sha256(abi.encode(ringSignatureData))`
+    pub journal: [u8; 32],
+    /// The user cycles of the guest execution
+    pub cycles: u64,
+}
+
+/// Runs the preflight checks on a base64-encoded signature.
+pub fn preflight(b64_signature: &str) -> Result<Preflight, PreflightError> {
+    let at = |stage: Stage| move |error: anyhow::Error| PreflightError { stage, error };
+
+    // the ring signature data and the verifier decode the signature independently
+    let ring_signature =
+        RingSignatureData::from_b64_lsag(b64_signature).map_err(at(Stage::Parsing))?;
+    let native_digest = match try_verify_b64_lsag(b64_signature) {
+        Ok(digest) => digest,
+        Err(LsagStatus::InvalidSignature) => {
+            return Err(at(Stage::NativeVerification)(anyhow!(
+                "the signature does not verify"
+            )))
+        }
+        Err(status) => {
+            return Err(at(Stage::Parsing)(anyhow!(
+                "invalid signature: {:?}",
+                status
+            )))
+        }
+    };
+
+    let (journal, cycles) = execute(b64_signature).map_err(at(Stage::GuestExecution))?;
+
+    let digest = ring_signature.digest();
+    let check_digest = || {
+        if journal != native_digest {
+            bail!(
+                "the guest journal {} differs from the native digest {}",
+                hex::encode(journal),
+                hex::encode(native_digest)
+            );
+        }
+        if journal != digest {
+            bail!(
+                "the journal {} differs from sha256(abi.encode(ringSignatureData)) {} for {}",
+                hex::encode(journal),
+                hex::encode(digest),
+                ring_signature.function_name()
+            );
+        }
+        Ok(())
+    };
+    check_digest().map_err(at(Stage::AbiDigest))?;
+
+    Ok(Preflight {
+        ring_signature,
+        journal,
+        cycles,
+    })
+}
+
+/// Executes the lsag_verifier guest and returns its journal and user cycles.
+fn execute(b64_signature: &str) -> anyhow::Result<([u8; 32], u64)> {
+    let env = ExecutorEnv::builder().write(&b64_signature)?.build()?;
+    let session = default_executor()
+        .execute(env, LSAG_VERIFIER_ELF)
+        .context("the guest failed")?;
+    let journal = session.journal.bytes.as_slice().try_into().map_err(|_| {
+        anyhow!(
+            "the journal is {} bytes long, not a 32-byte digest",
+            session.journal.bytes.len()
+        )
+    })?;
+    Ok((journal, session.cycles()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::demo;
+    use base64::engine::general_purpose;
+    use base64::Engine;
+    use serde_json::Value;
+
+    fn demo_with(field: &str, value: &str) -> String {
+        let json = general_purpose::STANDARD.decode(demo()).unwrap();
+        let mut json: Value = serde_json::from_slice(&json).unwrap();
+        json[field] = Value::String(value.to_string());
+        general_purpose::STANDARD.encode(json.to_string())
+    }
+
+    fn failed_stage(b64_signature: &str) -> Stage {
+        preflight(b64_signature).unwrap_err().stage
+    }
+
+    #[test]
+    fn malformed_signatures_fail_parsing() {
+        assert_eq!(failed_stage("not base64"), Stage::Parsing);
+        assert_eq!(failed_stage(&demo_with("keyImage", "02")), Stage::Parsing);
+        assert_eq!(failed_stage(&demo_with("c", "zz")), Stage::Parsing);
+    }
+
+    #[test]
+    fn forged_signatures_fail_native_verification() {
+        assert_eq!(
+            failed_stage(&demo_with("message", "Hello World!")),
+            Stage::NativeVerification
+        );
+    }
+
+    #[test]
+    fn valid_signatures_pass() {
+        // RISC0_SKIP_BUILD leaves the guest unbuilt
+        if LSAG_VERIFIER_ELF.is_empty() {
+            assert_eq!(failed_stage(demo()), Stage::GuestExecution);
+            return;
+        }
+        let preflight = preflight(demo()).unwrap();
+        assert_eq!(
+            hex::encode(preflight.journal),
+            "a1cb60bba5d3cda753cc11ee80044bb44345125fcc25fd6e8b8f924a4209518e"
+        );
+        assert_eq!(preflight.ring_signature.function_name(), "verifyRs");
+    }
+}
@@ -0,0 +1,219 @@
+// Network profiles, read from the config file of the deploy script (`script/config.toml`) so
+// that deploying and publishing share the chain ID and the contract addresses:
+//
+//     [profile.sepolia]
+//     chainId = 11155111
+//     riscZeroVerifierAddress = "0x..."  # optional, read by the deploy script
+//     rpcUrl = "https://..."
+//     lsagVerifierAddress = "0x..."      # the LsagVerifier contract called by the publisher
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use alloy_primitives::Address;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use url::Url;
+
+/// A network profile of the config file. Missing fields can be given on the command line.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub chain_id: Option<u64>,
+    pub risc_zero_verifier_address: Option<Address>,
+    pub rpc_url: Option<String>,
+    pub lsag_verifier_address: Option<Address>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    #[serde(default)]
+    profile: BTreeMap<String, Profile>,
+}
+
+impl Profile {
+    /// Reads the profile `name` of the config file at `path`.
+    pub fn load(path: &Path, name: &str) -> Result<Self> {
+        let config = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_config(&config, name).with_context(|| format!("in {}", path.display()))
+    }
+
+    fn from_config(config: &str, name: &str) -> Result<Self> {
+        let mut config: Config = toml::from_str(config).context("invalid config")?;
+        config.profile.remove(name).ok_or_else(|| {
+            anyhow!(
+                "no profile {:?}, the profiles are: {}",
+                name,
+                config.profile.into_keys().collect::<Vec<_>>().join(", ")
+            )
+        })
+    }
+}
+
+/// The network the publisher talks to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    pub chain_id: u64,
+    pub rpc_url: Url,
+    pub contract: Address,
+}
+
+/// The deployed contract, which is all that offline exports need.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    pub chain_id: u64,
+    pub contract: Address,
+}
+
+/// The network, from a profile of the config file and the options overriding it.
+#[derive(clap::Args, Debug, Clone)]
+pub struct NetworkArgs {
+    /// Network profile of the config file, as used by the deploy script
+    #[clap(long, env = "CONFIG_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Config file holding the network profiles
+    #[clap(long, default_value = "script/config.toml")]
+    pub config: PathBuf,
+
+    /// Ethereum chain ID, overrides the profile's `chainId`
+    #[clap(long)]
+    pub chain_id: Option<u64>,
+
+    /// Ethereum Node endpoint, overrides the profile's `rpcUrl`
+    #[clap(long)]
+    pub rpc_url: Option<Url>,
+
+    /// Application's contract address on Ethereum, overrides the profile's `lsagVerifierAddress`
+    #[clap(long)]
+    pub contract: Option<Address>,
+}
+
+impl NetworkArgs {
+    /// Resolves the network, the options taking precedence over the profile.
+    pub fn resolve(&self) -> Result<Network> {
+        self.resolve_with(self.load_profile()?)
+    }
+
+    /// Resolves the chain and the contract only, without requiring an RPC endpoint.
+    pub fn resolve_deployment(&self) -> Result<Deployment> {
+        self.deployment_with(&self.load_profile()?)
+    }
+
+    fn load_profile(&self) -> Result<Profile> {
+        match &self.profile {
+            Some(name) => Profile::load(&self.config, name),
+            None => Ok(Profile::default()),
+        }
+    }
+
+    fn missing(&self, option: &str, field: &str) -> anyhow::Error {
+        match &self.profile {
+            Some(name) => anyhow!("pass {} or set {} in the profile {:?}", option, field, name),
+            None => anyhow!("pass {} or select a profile with --profile", option),
+        }
+    }
+
+    fn resolve_with(&self, profile: Profile) -> Result<Network> {
+        let rpc_url = match (&self.rpc_url, &profile.rpc_url) {
+            (Some(rpc_url), _) => rpc_url.clone(),
+            (None, Some(rpc_url)) => {
+                Url::parse(rpc_url).with_context(|| format!("invalid rpcUrl {:?}", rpc_url))?
+            }
+            (None, None) => return Err(self.missing("--rpc-url", "rpcUrl")),
+        };
+        let deployment = self.deployment_with(&profile)?;
+        Ok(Network {
+            chain_id: deployment.chain_id,
+            rpc_url,
+            contract: deployment.contract,
+        })
+    }
+
+    fn deployment_with(&self, profile: &Profile) -> Result<Deployment> {
+        Ok(Deployment {
+            chain_id: self
+                .chain_id
+                .or(profile.chain_id)
+                .ok_or_else(|| self.missing("--chain-id", "chainId"))?,
+            contract: self
+                .contract
+                .or(profile.lsag_verifier_address)
+                .ok_or_else(|| self.missing("--contract", "lsagVerifierAddress"))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = include_str!("../../script/config.toml");
+
+    fn args(profile: Option<&str>) -> NetworkArgs {
+        NetworkArgs {
+            profile: profile.map(String::from),
+            config: PathBuf::new(),
+            chain_id: None,
+            rpc_url: None,
+            contract: None,
+        }
+    }
+
+    #[test]
+    fn the_deploy_config_parses() {
+        let profile = Profile::from_config(CONFIG, "sepolia").unwrap();
+        assert_eq!(profile.chain_id, Some(11155111));
+        assert!(profile.risc_zero_verifier_address.is_some());
+        assert!(Profile::from_config(CONFIG, "missing").is_err());
+    }
+
+    #[test]
+    fn options_override_the_profile() {
+        let contract = Address::repeat_byte(1);
+        let profile = Profile {
+            chain_id: Some(1),
+            rpc_url: Some("http://localhost:8545".into()),
+            lsag_verifier_address: Some(contract),
+            ..Profile::default()
+        };
+
+        let network = args(Some("local")).resolve_with(profile.clone()).unwrap();
+        assert_eq!(network.chain_id, 1);
+        assert_eq!(network.rpc_url.as_str(), "http://localhost:8545/");
+        assert_eq!(network.contract, contract);
+
+        let mut overridden = args(Some("local"));
+        overridden.chain_id = Some(31337);
+        overridden.contract = Some(Address::repeat_byte(2));
+        let network = overridden.resolve_with(profile).unwrap();
+        assert_eq!(network.chain_id, 31337);
+        assert_eq!(network.contract, Address::repeat_byte(2));
+    }
+
+    #[test]
+    fn missing_fields_are_reported() {
+        let error = args(None).resolve_with(Profile::default()).unwrap_err();
+        assert!(error.to_string().contains("--rpc-url"));
+        let profile = Profile {
+            rpc_url: Some("http://localhost:8545".into()),
+            ..Profile::default()
+        };
+        let error = args(Some("local")).resolve_with(profile).unwrap_err();
+        assert!(error.to_string().contains("chainId"));
+
+        // offline exports need no RPC endpoint
+        let mut offline = args(None);
+        offline.chain_id = Some(1);
+        offline.contract = Some(Address::repeat_byte(1));
+        assert_eq!(
+            offline
+                .deployment_with(&Profile::default())
+                .unwrap()
+                .chain_id,
+            1
+        );
+    }
+}
@@ -0,0 +1,263 @@
+// Proofs saved to disk between proving and submitting, so that a failed submission does not
+// lose the receipt. A proof directory holds:
+//
+// - `receipt.bin`: the bincode-serialized receipt
+// - `signature.b64`: the base64-encoded ring signature it proves
+// - `proof.json`: the kind of receipt, and the hex seal and journal as passed to the contract.
+//   Composite and succinct receipts have no seal, they cannot be verified on chain
+
+use std::fs;
+use std::path::Path;
+
+use alloy_primitives::hex;
+use anyhow::{anyhow, bail, Context, Result};
+use methods::LSAG_VERIFIER_ID;
+use risc0_ethereum_contracts::encode_seal;
+use risc0_zkvm::sha::{Digest, Digestible};
+use risc0_zkvm::{is_dev_mode, InnerReceipt, ProverOpts, Receipt, ReceiptClaim};
+use serde::Serialize;
+
+use crate::ring_signature::RingSignatureData;
+
+const RECEIPT: &str = "receipt.bin";
+const SIGNATURE: &str = "signature.b64";
+const PROOF: &str = "proof.json";
+
+/// A receipt and the ring signature it proves.
+#[derive(Debug)]
+pub struct SavedProof {
+    pub receipt: Receipt,
+    pub signature: String,
+}
+
+/// The kind of receipt to prove. In dev mode, the prover produces fake receipts whatever the
+/// requested kind.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptKind {
+    /// One receipt per segment, the fastest to prove
+    Composite,
+    /// A single STARK receipt, which can be aggregated
+    Succinct,
+    /// A Groth16 receipt, verified on chain
+    Groth16,
+}
+
+impl ReceiptKind {
+    pub fn prover_opts(&self) -> ProverOpts {
+        match self {
+            ReceiptKind::Composite => ProverOpts::composite(),
+            ReceiptKind::Succinct => ProverOpts::succinct(),
+            ReceiptKind::Groth16 => ProverOpts::groth16(),
+        }
+    }
+}
+
+/// The name of the kind of a receipt, `fake` for dev-mode receipts.
+pub fn receipt_kind_name(receipt: &Receipt) -> &'static str {
+    match receipt.inner {
+        InnerReceipt::Composite(_) => "composite",
+        InnerReceipt::Succinct(_) => "succinct",
+        InnerReceipt::Groth16(_) => "groth16",
+        InnerReceipt::Fake(_) => "fake",
+        _ => "unknown",
+    }
+}
+
+/// The kind of a receipt, and its seal and journal, hex encoded.
+#[derive(Serialize, Debug)]
+struct SealAndJournal {
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seal: Option<String>,
+    journal: String,
+}
+
+impl SavedProof {
+    /// Writes the proof to `directory`, creating it if needed. An existing proof is only
+    /// overwritten with `overwrite`.
+    pub fn save(&self, directory: &Path, overwrite: bool) -> Result<()> {
+        let receipt_path = directory.join(RECEIPT);
+        if receipt_path.exists() && !overwrite {
+            bail!(
+                "{} already holds a receipt, pass --force to overwrite it",
+                directory.display()
+            );
+        }
+        fs::create_dir_all(directory)
+            .with_context(|| format!("failed to create {}", directory.display()))?;
+
+        let seal_and_journal = SealAndJournal {
+            kind: receipt_kind_name(&self.receipt),
+            seal: self.seal().ok().map(hex::encode_prefixed),
+            journal: hex::encode_prefixed(&self.receipt.journal.bytes),
+        };
+        let files = [
+            (RECEIPT, bincode::serialize(&self.receipt)?),
+            (SIGNATURE, format!("{}\n", self.signature).into_bytes()),
+            (PROOF, serde_json::to_vec_pretty(&seal_and_journal)?),
+        ];
+        for (name, contents) in files {
+            let path = directory.join(name);
+            fs::write(&path, contents)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Reads a proof written by [`SavedProof::save`]. The receipt is not verified.
+    pub fn load(directory: &Path) -> Result<Self> {
+        let receipt_path = directory.join(RECEIPT);
+        let bytes = fs::read(&receipt_path)
+            .with_context(|| format!("failed to read {}", receipt_path.display()))?;
+        let receipt = bincode::deserialize(&bytes)
+            .with_context(|| format!("failed to decode {}", receipt_path.display()))?;
+
+        let signature_path = directory.join(SIGNATURE);
+        let signature = fs::read_to_string(&signature_path)
+            .with_context(|| format!("failed to read {}", signature_path.display()))?
+            .trim()
+            .to_string();
+        Ok(SavedProof { receipt, signature })
+    }
+
+    /// The seal passed to the contract. Groth16 receipts are verified by the Groth16 verifier,
+    /// and fake receipts by the mock verifier deployed in dev mode, whose seal is the claim
+    /// digest.
+    pub fn seal(&self) -> Result<Vec<u8>> {
+        match self.receipt.inner {
+            InnerReceipt::Groth16(_) | InnerReceipt::Fake(_) => encode_seal(&self.receipt),
+            _ => bail!(
+                "a {} receipt cannot be verified on chain, prove it with --receipt-kind groth16",
+                receipt_kind_name(&self.receipt)
+            ),
+        }
+    }
+
+    /// Checks the proof before any chain interaction: the receipt must prove the lsag_verifier
+    /// image ID, and its journal must be the digest of the ring signature data sent along with
+    /// it, otherwise the contract would revert. Returns that data.
+    pub fn verify(&self) -> Result<RingSignatureData> {
+        self.verify_with(is_dev_mode())
+    }
+
+    /// Checks the proof as [`SavedProof::verify`] does. Fake receipts are only accepted with
+    /// `dev_mode`, where their claim is checked instead of their proof.
+    pub fn verify_with(&self, dev_mode: bool) -> Result<RingSignatureData> {
+        let expected_image_id = Digest::from(LSAG_VERIFIER_ID);
+        let claim = self.receipt.claim().context("the receipt has no claim")?;
+        let image_id = claim
+            .as_value()
+            .map_err(|_| anyhow!("the receipt claim is pruned, its image ID is unknown"))?
+            .pre
+            .digest();
+        if image_id != expected_image_id {
+            bail!(
+                "the receipt proves image ID {}, not the lsag_verifier image ID {}",
+                image_id,
+                expected_image_id
+            );
+        }
+
+        let journal = &self.receipt.journal.bytes;
+        let journal: [u8; 32] = journal.as_slice().try_into().map_err(|_| {
+            anyhow!(
+                "the journal is {} bytes long, not a 32-byte digest",
+                journal.len()
+            )
+        })?;
+        let data = RingSignatureData::from_b64_lsag(&self.signature)?;
+        let digest = data.digest();
+        if journal != digest {
+            bail!(
+                "the journal {} does not match the digest {} of the ring signature data of {}",
+                hex::encode_prefixed(journal),
+                hex::encode_prefixed(digest),
+                data.function_name()
+            );
+        }
+
+        if let InnerReceipt::Fake(_) = self.receipt.inner {
+            if !dev_mode {
+                bail!("the receipt is a fake dev-mode receipt, it only verifies in dev mode");
+            }
+            // a fake receipt has no proof, only its claim can be checked
+            if claim.digest() != ReceiptClaim::ok(LSAG_VERIFIER_ID, journal.to_vec()).digest() {
+                bail!("the fake receipt does not claim a successful run committing its journal");
+            }
+        } else {
+            self.receipt
+                .verify(LSAG_VERIFIER_ID)
+                .context("the receipt does not verify")?;
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{demo_proof, fake_proof};
+
+    #[test]
+    fn saved_proofs_load_back() {
+        let proof = fake_proof([1u32; 8], vec![7u8; 32]);
+        let directory = std::env::temp_dir().join(format!("lsag-proof-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+
+        proof.save(&directory, false).unwrap();
+        assert!(proof.save(&directory, false).is_err());
+        proof.save(&directory, true).unwrap();
+        let loaded = SavedProof::load(&directory).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(loaded.signature, proof.signature);
+        assert_eq!(loaded.receipt.journal, proof.receipt.journal);
+        assert_eq!(
+            loaded.receipt.claim().unwrap().digest(),
+            proof.receipt.claim().unwrap().digest()
+        );
+    }
+
+    #[test]
+    fn proofs_of_another_image_are_rejected() {
+        let journal = demo_proof().receipt.journal.bytes;
+        let error = fake_proof([1u32; 8], journal).verify().unwrap_err();
+        assert!(error.to_string().contains("not the lsag_verifier image ID"));
+    }
+
+    #[test]
+    fn journals_of_other_data_are_rejected() {
+        let error = fake_proof(LSAG_VERIFIER_ID, vec![7u8; 32])
+            .verify()
+            .unwrap_err();
+        assert!(error.to_string().contains("does not match the digest"));
+        let error = fake_proof(LSAG_VERIFIER_ID, vec![7u8; 33])
+            .verify()
+            .unwrap_err();
+        assert!(error.to_string().contains("33 bytes long"));
+    }
+
+    #[test]
+    fn proofs_of_the_signature_data_verify() {
+        let data = demo_proof().verify_with(true).unwrap();
+        assert_eq!(data.function_name(), "verifyRs");
+    }
+
+    #[test]
+    fn fake_receipts_are_rejected_outside_dev_mode() {
+        let error = demo_proof().verify_with(false).unwrap_err();
+        assert!(error.to_string().contains("only verifies in dev mode"));
+    }
+
+    #[test]
+    fn fake_seals_hold_the_claim_digest() {
+        let proof = fake_proof(LSAG_VERIFIER_ID, vec![7u8; 32]);
+        let claim_digest = proof.receipt.claim().unwrap().digest();
+        // the selector of the mock verifier deployed in dev mode
+        assert_eq!(
+            proof.seal().unwrap(),
+            [&[0u8; 4], claim_digest.as_bytes()].concat()
+        );
+        assert_eq!(receipt_kind_name(&proof.receipt), "fake");
+    }
+}
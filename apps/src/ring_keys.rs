@@ -0,0 +1,158 @@
+// Recovering the secp256k1 public keys of Ethereum accounts from signed transactions and signed
+// messages they have already published, so that rings can be assembled from addresses.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use alloy::{
+    consensus::TxEnvelope, eips::eip2718::Decodable2718, signers::k256::ecdsa::VerifyingKey,
+};
+use alloy_primitives::{hex, Address, Signature};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+/// A signed payload from which the signer's public key can be recovered.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+#[allow(non_snake_case)]
+pub enum SignedEntry {
+    /// A raw signed transaction (legacy or EIP-2718 typed), hex encoded.
+    Transaction { rawTransaction: String },
+    /// An EIP-191 personal message and its 65-byte signature, hex encoded.
+    Message { message: String, signature: String },
+}
+
+/// Recovers the public key that produced the signature of `entry`.
+pub fn recover_key(entry: &SignedEntry) -> Result<VerifyingKey> {
+    match entry {
+        SignedEntry::Transaction { rawTransaction } => {
+            let bytes = hex::decode(rawTransaction).context("invalid transaction hex")?;
+            let tx = TxEnvelope::decode_2718(&mut bytes.as_slice())
+                .map_err(|e| anyhow!("failed to decode transaction: {}", e))?;
+            let signature = match &tx {
+                TxEnvelope::Legacy(tx) => tx.signature(),
+                TxEnvelope::Eip2930(tx) => tx.signature(),
+                TxEnvelope::Eip1559(tx) => tx.signature(),
+                TxEnvelope::Eip4844(tx) => tx.signature(),
+                TxEnvelope::Eip7702(tx) => tx.signature(),
+                _ => bail!("unsupported transaction type {}", tx.tx_type()),
+            };
+            Ok(signature.recover_from_prehash(&tx.signature_hash())?)
+        }
+        SignedEntry::Message { message, signature } => {
+            let signature = Signature::from_str(signature).context("invalid signature")?;
+            Ok(signature.recover_from_msg(message)?)
+        }
+    }
+}
+
+/// Recovers the key of each entry, as compressed hex keyed by address.
+pub fn recover_keys(entries: &[SignedEntry]) -> Result<BTreeMap<Address, String>> {
+    let mut keys = BTreeMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let key = recover_key(entry).with_context(|| format!("entry {}", i))?;
+        let address = Address::from_public_key(&key);
+        log::debug!("entry {} recovered {}", i, address);
+        keys.insert(address, hex::encode(key.to_encoded_point(true).as_bytes()));
+    }
+    Ok(keys)
+}
+
+/// Parses a list of addresses, one per line.
+pub fn parse_addresses(list: &str) -> Result<Vec<Address>> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| Address::from_str(line).with_context(|| format!("invalid address {}", line)))
+        .collect()
+}
+
+/// Keeps the keys of the `wanted` addresses only, failing if one of them was not recovered.
+pub fn select_keys(
+    mut keys: BTreeMap<Address, String>,
+    wanted: &[Address],
+) -> Result<BTreeMap<Address, String>> {
+    let missing: Vec<String> = wanted
+        .iter()
+        .filter(|address| !keys.contains_key(*address))
+        .map(|address| address.to_checksum(None))
+        .collect();
+    if !missing.is_empty() {
+        bail!("no signed payload found for {}", missing.join(", "));
+    }
+    keys.retain(|address, _| wanted.contains(address));
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNED: &str = include_str!("../fixtures/signed_payloads.json");
+
+    // the first three anvil accounts, which signed the fixtures
+    const ACCOUNT_0: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+    const ACCOUNT_1: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+    const ACCOUNT_2: &str = "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC";
+
+    fn entries() -> Vec<SignedEntry> {
+        serde_json::from_str(SIGNED).unwrap()
+    }
+
+    fn address(address: &str) -> Address {
+        Address::from_str(address).unwrap()
+    }
+
+    #[test]
+    fn keys_are_recovered_from_transactions_and_messages() {
+        let entries = entries();
+        let recovered: Vec<Address> = entries
+            .iter()
+            .map(|entry| Address::from_public_key(&recover_key(entry).unwrap()))
+            .collect();
+        // a legacy transaction, an EIP-1559 transaction and an EIP-191 message
+        assert_eq!(
+            recovered,
+            [address(ACCOUNT_0), address(ACCOUNT_1), address(ACCOUNT_2)]
+        );
+
+        let keys = recover_keys(&entries).unwrap();
+        assert_eq!(
+            keys[&address(ACCOUNT_0)],
+            "038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75"
+        );
+    }
+
+    #[test]
+    fn tampered_payloads_recover_other_keys() {
+        let SignedEntry::Message { signature, .. } = &entries()[2] else {
+            panic!("the third fixture is a message");
+        };
+        let tampered = SignedEntry::Message {
+            message: "Hello World!".to_string(),
+            signature: signature.clone(),
+        };
+        let recovered = Address::from_public_key(&recover_key(&tampered).unwrap());
+        assert_ne!(recovered, address(ACCOUNT_2));
+    }
+
+    #[test]
+    fn the_address_filter_requires_every_address() {
+        let keys = recover_keys(&entries()).unwrap();
+        let wanted = parse_addresses(&format!("{}\n\n  {}\n", ACCOUNT_0, ACCOUNT_2)).unwrap();
+        let selected = select_keys(keys.clone(), &wanted).unwrap();
+        // keyed by address, in byte order
+        assert_eq!(
+            selected.keys().copied().collect::<Vec<_>>(),
+            [address(ACCOUNT_2), address(ACCOUNT_0)]
+        );
+
+        // an address without a signed payload
+        let wanted = [address(ACCOUNT_0), Address::repeat_byte(1)];
+        let error = select_keys(keys, &wanted).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains(&Address::repeat_byte(1).to_checksum(None)));
+        assert!(parse_addresses("not an address").is_err());
+    }
+}
@@ -0,0 +1,301 @@
+// The ring signature data passed to the contract along with the proof, derived from the LSAG.
+// The contract checks that the journal holds the sha256 digest of its ABI encoding, so it is
+// built exactly as `abi_encode_minimal_lsag` encodes the verified signature in the guest.
+
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_sol_types::{SolCall, SolValue};
+use anyhow::{anyhow, Context, Result};
+use lsag_core::lsag_verifier::conversion::MessageEncoding;
+use lsag_core::lsag_verifier::lsag_verifier::{decode_b64_lsag, resolve_message};
+use lsag_core::lsag_verifier::minimal::MinimalMessage;
+use lsag_core::utils::{decode_ring, point_coordinates};
+use sha2::{Digest, Sha256};
+
+use crate::contract::ILsagVerifier;
+
+/// The ring signature data of each verification function of the contract.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RingSignatureData {
+    /// A UTF-8 message, verified with `verifyRs`
+    Utf8(ILsagVerifier::RingSignatureData),
+    /// A hex or base64 encoded binary message, verified with `verifyBytesRs`
+    Bytes(ILsagVerifier::BytesRingSignatureData),
+    /// A message given as its sha256 digest, verified with `verifyPrehashedRs`
+    Prehashed(ILsagVerifier::PrehashedRingSignatureData),
+    /// An EIP-191 personal message, verified with `verifyEip191Rs`
+    Eip191(ILsagVerifier::Eip191RingSignatureData),
+    /// An EIP-712 typed data payload, verified with `verifyEip712Rs`
+    Eip712(ILsagVerifier::Eip712RingSignatureData),
+}
+
+impl RingSignatureData {
+    /// Decodes a base64-encoded LSAG into the data of its verification function. The signature
+    /// itself is not verified.
+    pub fn from_b64_lsag(b64_signature: &str) -> Result<Self> {
+        let json = decode_b64_lsag(b64_signature).context("the signature is not a valid LSAG")?;
+        let (_, message) =
+            resolve_message(&json).context("the signature has an invalid message")?;
+        let ring: Vec<ILsagVerifier::Point> = decode_ring(&json.ring)
+            .map_err(|e| anyhow!("invalid ring member: {}", e))?
+            .iter()
+            .map(|member| to_point(point_coordinates(member)))
+            .collect();
+        let key_image = json
+            .keyImage
+            .decode()
+            .map_err(|e| anyhow!("invalid key image: {}", e))?;
+        let key_image = to_point(point_coordinates(&key_image));
+        let linkability_flag = json.linkabilityFlag.clone();
+
+        Ok(match message {
+            MinimalMessage::Full(message) if json.messageEncoding == MessageEncoding::Utf8 => {
+                RingSignatureData::Utf8(ILsagVerifier::RingSignatureData {
+                    // the UTF-8 encoding was decoded from a string
+                    message: String::from_utf8(message)?,
+                    linkabilityFlag: linkability_flag,
+                    keyImage: key_image,
+                    ring,
+                })
+            }
+            MinimalMessage::Full(message) => {
+                RingSignatureData::Bytes(ILsagVerifier::BytesRingSignatureData {
+                    message: Bytes::from(message),
+                    linkabilityFlag: linkability_flag,
+                    keyImage: key_image,
+                    ring,
+                })
+            }
+            MinimalMessage::Digest(digest) => {
+                RingSignatureData::Prehashed(ILsagVerifier::PrehashedRingSignatureData {
+                    messageDigest: B256::from(digest),
+                    linkabilityFlag: linkability_flag,
+                    keyImage: key_image,
+                    ring,
+                })
+            }
+            MinimalMessage::Eip191 { message, digest } => {
+                RingSignatureData::Eip191(ILsagVerifier::Eip191RingSignatureData {
+                    messageDigest: B256::from(digest),
+                    message: Bytes::from(message),
+                    linkabilityFlag: linkability_flag,
+                    keyImage: key_image,
+                    ring,
+                })
+            }
+            MinimalMessage::Eip712 {
+                domain_separator,
+                struct_hash,
+            } => RingSignatureData::Eip712(ILsagVerifier::Eip712RingSignatureData {
+                domainSeparator: B256::from(domain_separator),
+                structHash: B256::from(struct_hash),
+                linkabilityFlag: linkability_flag,
+                keyImage: key_image,
+                ring,
+            }),
+        })
+    }
+
+    /// The name of the contract function verifying this data.
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            RingSignatureData::Utf8(_) => "verifyRs",
+            RingSignatureData::Bytes(_) => "verifyBytesRs",
+            RingSignatureData::Prehashed(_) => "verifyPrehashedRs",
+            RingSignatureData::Eip191(_) => "verifyEip191Rs",
+            RingSignatureData::Eip712(_) => "verifyEip712Rs",
+        }
+    }
+
+    /// The name of the contract function verifying this data when proven in chunks.
+    pub fn chunked_function_name(&self) -> &'static str {
+        match self {
+            RingSignatureData::Utf8(_) => "verifyChunkedRs",
+            RingSignatureData::Bytes(_) => "verifyChunkedBytesRs",
+            RingSignatureData::Prehashed(_) => "verifyChunkedPrehashedRs",
+            RingSignatureData::Eip191(_) => "verifyChunkedEip191Rs",
+            RingSignatureData::Eip712(_) => "verifyChunkedEip712Rs",
+        }
+    }
+
+    /// The data as `abi.encode`d by the contract, whose sha256 digest is the journal.
+    pub fn abi_encode(&self) -> Vec<u8> {
+        match self {
+            RingSignatureData::Utf8(data) => data.abi_encode(),
+            RingSignatureData::Bytes(data) => data.abi_encode(),
+            RingSignatureData::Prehashed(data) => data.abi_encode(),
+            RingSignatureData::Eip191(data) => data.abi_encode(),
+            RingSignatureData::Eip712(data) => data.abi_encode(),
+        }
+    }
+
+    /// The sha256 digest of the ABI encoded data, which the guest commits as the journal.
+    pub fn digest(&self) -> [u8; 32] {
+        Sha256::digest(self.abi_encode()).into()
+    }
+
+    /// The calldata of the call to the verification function of this data.
+    pub fn calldata(&self, seal: Bytes, journal: Bytes) -> Vec<u8> {
+        match self.clone() {
+            RingSignatureData::Utf8(data) => ILsagVerifier::verifyRsCall {
+                seal,
+                journal,
+                _ringSignatureData: data,
+            }
+            .abi_encode(),
+            RingSignatureData::Bytes(data) => ILsagVerifier::verifyBytesRsCall {
+                seal,
+                journal,
+                _ringSignatureData: data,
+            }
+            .abi_encode(),
+            RingSignatureData::Prehashed(data) => ILsagVerifier::verifyPrehashedRsCall {
+                seal,
+                journal,
+                _ringSignatureData: data,
+            }
+            .abi_encode(),
+            RingSignatureData::Eip191(data) => ILsagVerifier::verifyEip191RsCall {
+                seal,
+                journal,
+                _ringSignatureData: data,
+            }
+            .abi_encode(),
+            RingSignatureData::Eip712(data) => ILsagVerifier::verifyEip712RsCall {
+                seal,
+                journal,
+                _ringSignatureData: data,
+            }
+            .abi_encode(),
+        }
+    }
+
+    /// Decodes the value returned by the verification function of this data, the verified
+    /// ring signature data.
+    pub fn decode_return(&self, output: &[u8]) -> Result<String> {
+        let value = match self {
+            RingSignatureData::Utf8(_) => {
+                format!(
+                    "{:?}",
+                    ILsagVerifier::verifyRsCall::abi_decode_returns(output, true)?._0
+                )
+            }
+            RingSignatureData::Bytes(_) => format!(
+                "{:?}",
+                ILsagVerifier::verifyBytesRsCall::abi_decode_returns(output, true)?._0
+            ),
+            RingSignatureData::Prehashed(_) => format!(
+                "{:?}",
+                ILsagVerifier::verifyPrehashedRsCall::abi_decode_returns(output, true)?._0
+            ),
+            RingSignatureData::Eip191(_) => format!(
+                "{:?}",
+                ILsagVerifier::verifyEip191RsCall::abi_decode_returns(output, true)?._0
+            ),
+            RingSignatureData::Eip712(_) => format!(
+                "{:?}",
+                ILsagVerifier::verifyEip712RsCall::abi_decode_returns(output, true)?._0
+            ),
+        };
+        Ok(value)
+    }
+}
+
+fn to_point((x, y): ([u8; 32], [u8; 32])) -> ILsagVerifier::Point {
+    ILsagVerifier::Point {
+        x: U256::from_be_bytes(x),
+        y: U256::from_be_bytes(y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose;
+    use base64::Engine;
+    use lsag_core::lsag_verifier::minimal::{digest_minimal_lsag, MinimalLsag};
+    use lsag_core::lsag_verifier::try_verify_b64_lsag;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/signatures");
+
+    /// (fixture, contract function, whether the signature verifies). The EIP-191 and EIP-712
+    /// fixtures reuse the demo scalars, so they only exercise the decoding of their messages.
+    const SIGNATURES: [(&str, &str, bool); 7] = [
+        ("demo.json", "verifyRs", true),
+        ("demo_coordinates.json", "verifyRs", true),
+        ("demo_uncompressed.json", "verifyRs", true),
+        ("demo_hex_message.json", "verifyBytesRs", true),
+        ("demo_prehashed.json", "verifyPrehashedRs", true),
+        ("demo_eip191.json", "verifyEip191Rs", false),
+        ("eip712_mail.json", "verifyEip712Rs", false),
+    ];
+
+    fn fixture(name: &str) -> String {
+        general_purpose::STANDARD.encode(std::fs::read(format!("{}/{}", FIXTURES, name)).unwrap())
+    }
+
+    /// The digest of the minimal LSAG, as committed by the guest.
+    fn minimal_lsag_digest(b64_signature: &str) -> [u8; 32] {
+        let json = decode_b64_lsag(b64_signature).unwrap();
+        digest_minimal_lsag(&MinimalLsag {
+            message: resolve_message(&json).unwrap().1,
+            key_image: json.keyImage.decode().unwrap(),
+            linkability_flag: Some(&json.linkabilityFlag),
+            ring: decode_ring(&json.ring).unwrap(),
+        })
+    }
+
+    #[test]
+    fn abi_encoding_matches_the_journal() {
+        for (name, function, verifies) in SIGNATURES {
+            let b64 = fixture(name);
+            let data = RingSignatureData::from_b64_lsag(&b64).unwrap();
+            let digest = data.digest();
+            assert_eq!(digest, minimal_lsag_digest(&b64), "{}", name);
+            assert_eq!(data.function_name(), function, "{}", name);
+            assert_eq!(
+                data.chunked_function_name(),
+                function.replacen("verify", "verifyChunked", 1)
+            );
+            let result = try_verify_b64_lsag(&b64);
+            assert_eq!(result.is_ok(), verifies, "{}: {:?}", name, result);
+            if let Ok(journal) = result {
+                assert_eq!(digest, journal, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn demo_ring_matches_its_coordinates() {
+        let data = RingSignatureData::from_b64_lsag(&fixture("demo.json"));
+        let RingSignatureData::Utf8(data) = data.unwrap() else {
+            panic!("the demo message is UTF-8");
+        };
+        assert_eq!(data.message, "Hello World");
+        assert_eq!(data.linkabilityFlag, "linkability");
+        assert_eq!(data.ring.len(), 4);
+        assert_eq!(
+            data.ring[0].y,
+            U256::from_str_radix(
+                "2c9535b5f71409cf334b639de2d39074ea35c3057857e32ca5487f5d3c68d982",
+                16
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            data.keyImage.x,
+            U256::from_str_radix(
+                "e7ff3490ee7db33750fe9a7091d82dca952e6d22a7fd4df97d0cbf8f7cb46ad2",
+                16
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_signatures_are_rejected() {
+        assert!(RingSignatureData::from_b64_lsag("not base64").is_err());
+        let json = r#"{"message":"Hello World","ring":["02"],"c":"","responses":[],"keyImage":"","linkabilityFlag":""}"#;
+        let b64 = general_purpose::STANDARD.encode(json);
+        assert!(RingSignatureData::from_b64_lsag(&b64).is_err());
+    }
+}
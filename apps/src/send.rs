@@ -0,0 +1,424 @@
+// State-changing submissions. The transaction is signed on the host with a fixed nonce and sent
+// raw, so that retrying a failed broadcast resends the very same transaction and can never send
+// it twice. RPC requests failing for transient reasons (rate limits, unavailable or unreachable
+// nodes, timeouts) are retried with an exponential backoff.
+
+use std::future::{Future, IntoFuture};
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use alloy::eips::eip2718::Encodable2718;
+use alloy::network::{Ethereum, EthereumWallet, TransactionBuilder};
+use alloy::providers::{PendingTransactionBuilder, PendingTransactionError, Provider};
+use alloy::rpc::types::{TransactionReceipt, TransactionRequest};
+use alloy::transports::http::reqwest;
+use alloy::transports::{RpcError, Transport, TransportError, TransportErrorKind};
+use alloy_primitives::TxHash;
+use anyhow::{bail, Context, Result};
+
+/// The margin added to the estimated gas, in percent.
+const GAS_MARGIN_PERCENT: u64 = 20;
+
+/// Whether to send a transaction rather than only call the contract, and how.
+#[derive(clap::Args, Debug, Clone)]
+pub struct SendArgs {
+    /// Send a transaction recording the verification on chain, instead of an eth_call
+    #[clap(long)]
+    pub send: bool,
+
+    /// Gas limit of the transaction. Estimated with a 20% margin by default
+    #[clap(long, requires = "send")]
+    pub gas_limit: Option<u64>,
+
+    /// Maximum fee per gas of the transaction, in wei. Estimated by default
+    #[clap(long, requires = "send")]
+    pub max_fee_per_gas: Option<u128>,
+
+    /// Maximum priority fee per gas of the transaction, in wei. Estimated by default
+    #[clap(long, requires = "send")]
+    pub max_priority_fee_per_gas: Option<u128>,
+
+    /// Number of confirmations to wait for
+    #[clap(long, default_value = "1", requires = "send")]
+    pub confirmations: u64,
+
+    /// Seconds to wait for the confirmations before giving up
+    #[clap(long, default_value = "300", requires = "send")]
+    pub timeout: u64,
+
+    /// Number of retries of the RPC requests failing for transient reasons
+    #[clap(long, default_value = "3")]
+    pub retries: u32,
+}
+
+impl SendArgs {
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            retries: self.retries,
+            initial_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+/// How many times to retry a request, waiting twice as long before each retry.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub initial_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Runs `request` until it succeeds, fails for a reason `transient` does not accept, or
+    /// exhausts the retries.
+    pub async fn run<T, E, F, Fut>(
+        &self,
+        what: &str,
+        transient: impl Fn(&E) -> bool,
+        mut request: F,
+    ) -> Result<T, E>
+    where
+        E: std::fmt::Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut backoff = self.initial_backoff;
+        for _ in 0..self.retries {
+            match request().await {
+                Err(error) if transient(&error) => {
+                    eprintln!("{} failed: {}, retrying in {:?}", what, error, backoff);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                result => return result,
+            }
+        }
+        request().await
+    }
+
+    /// Runs an RPC request, retrying it on transient errors.
+    pub async fn rpc<T, F, Fut>(&self, what: &str, request: F) -> Result<T, TransportError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, TransportError>>,
+    {
+        self.run(what, is_transient, request).await
+    }
+}
+
+/// Whether an RPC request may succeed when retried: rate limits, unavailable nodes, connection
+/// failures and timeouts. Error responses of the node, such as reverts, are final.
+pub fn is_transient(error: &TransportError) -> bool {
+    match error {
+        RpcError::ErrorResp(payload) => payload.is_retry_err(),
+        RpcError::Transport(TransportErrorKind::BackendGone) => true,
+        RpcError::Transport(TransportErrorKind::Custom(error)) => is_connection_error(&**error),
+        RpcError::Transport(kind) => kind.is_retry_err(),
+        _ => false,
+    }
+}
+
+/// Whether a custom transport error, or one of its sources, is a failure to connect to the
+/// node or a timeout.
+fn is_connection_error(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+            if error.is_connect() || error.is_timeout() {
+                return true;
+            }
+        }
+        if let Some(error) = error.downcast_ref::<std::io::Error>() {
+            if matches!(
+                error.kind(),
+                ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::NotConnected
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::TimedOut
+            ) {
+                return true;
+            }
+        }
+        source = error.source();
+    }
+    false
+}
+
+/// Whether waiting for a receipt failed on a transient RPC error.
+fn is_transient_wait(error: &PendingTransactionError) -> bool {
+    matches!(error, PendingTransactionError::TransportError(error) if is_transient(error))
+}
+
+/// Whether a broadcast failed because the node already has the transaction, as happens when
+/// the broadcast succeeded but its response was lost.
+fn is_already_known(error: &TransportError) -> bool {
+    match error {
+        RpcError::ErrorResp(payload) => {
+            let message = payload.message.to_lowercase();
+            message.contains("already known") || message.contains("known transaction")
+        }
+        _ => false,
+    }
+}
+
+/// The EIP-1559 fees of a transaction, in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+/// Resolves the fees from the options, falling back to the estimated fees. An estimated
+/// priority fee is capped by the max fee.
+pub fn resolve_fees(
+    max_fee_per_gas: Option<u128>,
+    max_priority_fee_per_gas: Option<u128>,
+    estimate: Option<Fees>,
+) -> Result<Fees> {
+    let estimated = || estimate.context("the fees were not estimated");
+    let max_fee_per_gas = match max_fee_per_gas {
+        Some(max_fee_per_gas) => max_fee_per_gas,
+        None => estimated()?.max_fee_per_gas,
+    };
+    let max_priority_fee_per_gas = match max_priority_fee_per_gas {
+        Some(max_priority_fee_per_gas) => max_priority_fee_per_gas,
+        None => estimated()?.max_priority_fee_per_gas.min(max_fee_per_gas),
+    };
+    if max_priority_fee_per_gas > max_fee_per_gas {
+        bail!(
+            "the max priority fee per gas {} exceeds the max fee per gas {}",
+            max_priority_fee_per_gas,
+            max_fee_per_gas
+        );
+    }
+    Ok(Fees {
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+    })
+}
+
+/// Signs `request` with `wallet`, sends it and waits for its confirmations. The request holds
+/// the sender, the contract and the calldata; the chain ID, nonce, gas and fees are filled in.
+pub async fn send<T, P>(
+    provider: &P,
+    wallet: &EthereumWallet,
+    request: TransactionRequest,
+    chain_id: u64,
+    args: &SendArgs,
+) -> Result<TransactionReceipt>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum>,
+{
+    let policy = args.retry_policy();
+    let from = request.from.context("the transaction has no sender")?;
+
+    let gas_limit = match args.gas_limit {
+        Some(gas_limit) => gas_limit,
+        None => {
+            let estimate = policy
+                .rpc("gas estimation", || {
+                    provider.estimate_gas(&request).into_future()
+                })
+                .await
+                .context("failed to estimate the gas")?;
+            estimate + estimate * GAS_MARGIN_PERCENT / 100
+        }
+    };
+    let estimate = match (args.max_fee_per_gas, args.max_priority_fee_per_gas) {
+        (Some(_), Some(_)) => None,
+        _ => {
+            let estimate = policy
+                .rpc("fee estimation", || provider.estimate_eip1559_fees(None))
+                .await
+                .context("failed to estimate the fees")?;
+            Some(Fees {
+                max_fee_per_gas: estimate.max_fee_per_gas,
+                max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+            })
+        }
+    };
+    let fees = resolve_fees(
+        args.max_fee_per_gas,
+        args.max_priority_fee_per_gas,
+        estimate,
+    )?;
+    let nonce = policy
+        .rpc("nonce query", || {
+            provider.get_transaction_count(from).pending().into_future()
+        })
+        .await
+        .context("failed to get the nonce of the sender")?;
+
+    let transaction = request
+        .with_chain_id(chain_id)
+        .with_nonce(nonce)
+        .with_gas_limit(gas_limit)
+        .with_max_fee_per_gas(fees.max_fee_per_gas)
+        .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+        .build(wallet)
+        .await
+        .context("failed to sign the transaction")?;
+    let hash = *transaction.tx_hash();
+    let raw = transaction.encoded_2718();
+    println!(
+        "Sending transaction {} (nonce {}, gas limit {}, max fee per gas {}, max priority fee \
+         per gas {})",
+        hash, nonce, gas_limit, fees.max_fee_per_gas, fees.max_priority_fee_per_gas
+    );
+
+    let broadcast = policy
+        .rpc("broadcast", || async {
+            match provider.send_raw_transaction(&raw).await {
+                Err(error) if is_already_known(&error) => Ok(()),
+                result => result.map(|_| ()),
+            }
+        })
+        .await;
+    if let Err(error) = broadcast {
+        // the node may have accepted the transaction and the response been lost: resending it
+        // would then fail with a nonce too low, so look it up before giving up
+        let lookup = policy
+            .rpc("transaction lookup", || {
+                provider.get_transaction_by_hash(hash).into_future()
+            })
+            .await;
+        if !matches!(lookup, Ok(Some(_))) {
+            return Err(error).context("failed to send the transaction");
+        }
+    }
+    println!("Transaction hash: {}", hash);
+
+    wait_for_receipt(provider, hash, args).await
+}
+
+/// Waits for the confirmations of a sent transaction and checks that it succeeded.
+async fn wait_for_receipt<T, P>(
+    provider: &P,
+    hash: TxHash,
+    args: &SendArgs,
+) -> Result<TransactionReceipt>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum>,
+{
+    let receipt = args
+        .retry_policy()
+        .run("waiting for the receipt", is_transient_wait, || {
+            PendingTransactionBuilder::new(provider.root(), hash)
+                .with_required_confirmations(args.confirmations)
+                .with_timeout(Some(Duration::from_secs(args.timeout)))
+                .get_receipt()
+        })
+        .await
+        .with_context(|| format!("failed to get the receipt of {}", hash))?;
+    if !receipt.status() {
+        bail!(
+            "the transaction {} reverted in block {}",
+            hash,
+            receipt
+                .block_number
+                .map_or("unknown".to_string(), |block| block.to_string())
+        );
+    }
+    Ok(receipt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn error_response(code: i64, message: &str) -> TransportError {
+        let payload = serde_json::json!({ "code": code, "message": message });
+        RpcError::ErrorResp(serde_json::from_value(payload).unwrap())
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        assert!(is_transient(&TransportErrorKind::http_error(
+            429,
+            String::new()
+        )));
+        assert!(is_transient(&TransportErrorKind::http_error(
+            503,
+            String::new()
+        )));
+        assert!(is_transient(&TransportErrorKind::custom(
+            std::io::Error::from(ErrorKind::ConnectionRefused)
+        )));
+        assert!(is_transient(&TransportErrorKind::custom(
+            std::io::Error::from(ErrorKind::TimedOut)
+        )));
+        assert!(!is_transient(&TransportErrorKind::custom(
+            std::io::Error::from(ErrorKind::InvalidData)
+        )));
+        assert!(!is_transient(&TransportErrorKind::custom_str(
+            "unexpected response"
+        )));
+        assert!(is_transient(&error_response(-32005, "limit exceeded")));
+        assert!(!is_transient(&error_response(3, "execution reverted")));
+        assert!(!is_transient(&error_response(
+            -32000,
+            "insufficient funds for gas * price + value"
+        )));
+        assert!(is_already_known(&error_response(-32000, "already known")));
+    }
+
+    #[test]
+    fn retries_back_off_until_success_or_exhaustion() {
+        let policy = RetryPolicy {
+            retries: 3,
+            initial_backoff: Duration::ZERO,
+        };
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let run = |failures: u32, transient: bool| {
+            let calls = Cell::new(0);
+            let result = runtime.block_on(policy.run(
+                "test",
+                |_: &String| transient,
+                || {
+                    calls.set(calls.get() + 1);
+                    let result = if calls.get() > failures {
+                        Ok(())
+                    } else {
+                        Err("failure".to_string())
+                    };
+                    async move { result }
+                },
+            ));
+            (result.is_ok(), calls.get())
+        };
+
+        assert_eq!(run(0, true), (true, 1));
+        assert_eq!(run(2, true), (true, 3));
+        assert_eq!(run(10, true), (false, 4));
+        assert_eq!(run(10, false), (false, 1));
+    }
+
+    #[test]
+    fn fees_fall_back_to_the_estimate() {
+        let estimate = Fees {
+            max_fee_per_gas: 30,
+            max_priority_fee_per_gas: 2,
+        };
+        assert_eq!(resolve_fees(None, None, Some(estimate)).unwrap(), estimate);
+        // a lower max fee caps the estimated priority fee
+        assert_eq!(
+            resolve_fees(Some(1), None, Some(estimate)).unwrap(),
+            Fees {
+                max_fee_per_gas: 1,
+                max_priority_fee_per_gas: 1
+            }
+        );
+        assert_eq!(
+            resolve_fees(Some(50), Some(5), None).unwrap(),
+            Fees {
+                max_fee_per_gas: 50,
+                max_priority_fee_per_gas: 5
+            }
+        );
+        assert!(resolve_fees(Some(5), Some(50), None).is_err());
+    }
+}
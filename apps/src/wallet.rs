@@ -0,0 +1,201 @@
+// The signer of the publisher, loaded from an encrypted JSON keystore (the Web3 Secret Storage
+// format used by geth and foundry). Private keys are never passed on the command line: the
+// keystore password is read from a file or from the `KEYSTORE_PASSWORD` environment variable.
+
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{bail, Context, Result};
+
+/// The environment variable holding the keystore password, when no password file is given.
+pub const PASSWORD_ENV: &str = "KEYSTORE_PASSWORD";
+
+/// Where to read the keystore password from. Without `--keystore-password-file`, it is read
+/// from `KEYSTORE_PASSWORD`.
+#[derive(clap::Args, Debug, Clone)]
+pub struct PasswordArgs {
+    /// File holding the keystore password, instead of the KEYSTORE_PASSWORD environment variable
+    #[clap(long, env = "KEYSTORE_PASSWORD_FILE")]
+    pub keystore_password_file: Option<PathBuf>,
+}
+
+impl PasswordArgs {
+    /// Reads the password. A single trailing newline of the password file is dropped.
+    pub fn read(&self) -> Result<String> {
+        let password = match &self.keystore_password_file {
+            Some(path) => {
+                let password = fs::read_to_string(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                let password = password.strip_suffix('\n').unwrap_or(&password);
+                password.strip_suffix('\r').unwrap_or(password).to_string()
+            }
+            None => std::env::var(PASSWORD_ENV).with_context(|| {
+                format!(
+                    "no keystore password: pass --keystore-password-file or set {}",
+                    PASSWORD_ENV
+                )
+            })?,
+        };
+        if password.is_empty() {
+            bail!("the keystore password is empty");
+        }
+        Ok(password)
+    }
+}
+
+/// The account signing the transactions, from an encrypted keystore.
+#[derive(clap::Args, Debug, Clone)]
+pub struct WalletArgs {
+    /// Encrypted JSON keystore of the account sending the transactions
+    #[clap(long, env = "ETH_KEYSTORE")]
+    pub keystore: PathBuf,
+
+    #[clap(flatten)]
+    pub password: PasswordArgs,
+}
+
+impl WalletArgs {
+    /// Decrypts the keystore.
+    pub fn signer(&self) -> Result<PrivateKeySigner> {
+        decrypt_keystore(&self.keystore, &self.password.read()?)
+    }
+}
+
+/// Decrypts the keystore at `path`.
+pub fn decrypt_keystore(path: &Path, password: &str) -> Result<PrivateKeySigner> {
+    if !path.is_file() {
+        bail!("no keystore at {}", path.display());
+    }
+    PrivateKeySigner::decrypt_keystore(path, password).with_context(|| {
+        format!(
+            "failed to decrypt {}, is the password right?",
+            path.display()
+        )
+    })
+}
+
+/// Encrypts `signer` into a new keystore at `path`. An existing file is only overwritten with
+/// `overwrite`.
+pub fn encrypt_keystore(
+    signer: &PrivateKeySigner,
+    path: &Path,
+    password: &str,
+    overwrite: bool,
+) -> Result<()> {
+    if path.exists() && !overwrite {
+        bail!(
+            "{} already exists, pass --force to overwrite it",
+            path.display()
+        );
+    }
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("invalid keystore path {}", path.display()))?;
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(directory)
+        .with_context(|| format!("failed to create {}", directory.display()))?;
+    PrivateKeySigner::encrypt_keystore(
+        directory,
+        &mut rand::thread_rng(),
+        signer.to_bytes(),
+        password,
+        Some(name),
+    )
+    .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Reads a hex private key, `0x` prefixed or not, from a file or from stdin when `path` is
+/// `None` or `-`.
+pub fn read_private_key(path: Option<&Path>) -> Result<PrivateKeySigner> {
+    let key = match path {
+        Some(path) if path.as_os_str() != "-" => fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?,
+        _ => {
+            let mut stdin = io::stdin();
+            if path.is_none() && stdin.is_terminal() {
+                bail!("no private key given: pass --private-key-file or stdin");
+            }
+            let mut key = String::new();
+            stdin
+                .read_to_string(&mut key)
+                .context("failed to read the private key from stdin")?;
+            key
+        }
+    };
+    // the error would quote the key
+    PrivateKeySigner::from_str(key.trim()).map_err(|_| anyhow::anyhow!("invalid private key"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the first anvil account
+    const KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("lsag-wallet-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[test]
+    fn keystores_decrypt_to_their_key() {
+        let directory = temp_dir("keystore");
+        let path = directory.join("publisher.json");
+        let signer = PrivateKeySigner::from_str(KEY).unwrap();
+
+        encrypt_keystore(&signer, &path, "password", false).unwrap();
+        assert!(encrypt_keystore(&signer, &path, "password", false).is_err());
+        let decrypted = decrypt_keystore(&path, "password").unwrap();
+        let error = decrypt_keystore(&path, "wrong").unwrap_err();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(decrypted.address(), signer.address());
+        assert!(error.to_string().contains("is the password right?"));
+    }
+
+    #[test]
+    fn password_files_drop_their_trailing_newline() {
+        let directory = temp_dir("password");
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("password");
+        let args = PasswordArgs {
+            keystore_password_file: Some(path.clone()),
+        };
+
+        fs::write(&path, "secret \r\n").unwrap();
+        let password = args.read().unwrap();
+        fs::write(&path, "\n").unwrap();
+        let error = args.read().unwrap_err();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(password, "secret ");
+        assert!(error.to_string().contains("empty"));
+    }
+
+    #[test]
+    fn private_keys_are_not_echoed() {
+        let directory = temp_dir("key");
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("key");
+
+        fs::write(&path, format!("{}\n", KEY)).unwrap();
+        let signer = read_private_key(Some(&path)).unwrap();
+        fs::write(&path, &KEY[..40]).unwrap();
+        let error = read_private_key(Some(&path)).unwrap_err();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(signer, PrivateKeySigner::from_str(KEY).unwrap());
+        assert!(!format!("{:#}", error).contains(&KEY[2..40]));
+    }
+}
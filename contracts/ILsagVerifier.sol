@@ -14,10 +14,122 @@ interface ILsagVerifier {
         Point[] ring;
     }
 
+    struct BytesRingSignatureData {
+        bytes message;
+        string linkabilityFlag;
+        Point keyImage;
+        Point[] ring;
+    }
+
+    struct PrehashedRingSignatureData {
+        bytes32 messageDigest;
+        string linkabilityFlag;
+        Point keyImage;
+        Point[] ring;
+    }
+
+    struct Eip191RingSignatureData {
+        bytes32 messageDigest;
+        bytes message;
+        string linkabilityFlag;
+        Point keyImage;
+        Point[] ring;
+    }
+
+    struct Eip712RingSignatureData {
+        bytes32 domainSeparator;
+        bytes32 structHash;
+        string linkabilityFlag;
+        Point keyImage;
+        Point[] ring;
+    }
+
     // verify a lsag
     function verifyRs(
         bytes calldata seal,
         bytes calldata journal,
         RingSignatureData memory _ringSignatureData
     ) external view returns (RingSignatureData memory);
+
+    // verify a lsag over a binary message
+    function verifyBytesRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        BytesRingSignatureData memory _ringSignatureData
+    ) external view returns (BytesRingSignatureData memory);
+
+    // verify a lsag given only the sha256 digest of its message
+    function verifyPrehashedRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        PrehashedRingSignatureData memory _ringSignatureData
+    ) external view returns (PrehashedRingSignatureData memory);
+
+    // verify a lsag over an EIP-191 personal message
+    function verifyEip191Rs(
+        bytes calldata seal,
+        bytes calldata journal,
+        Eip191RingSignatureData memory _ringSignatureData
+    ) external view returns (Eip191RingSignatureData memory);
+
+    // verify a lsag over an EIP-712 typed data payload
+    function verifyEip712Rs(
+        bytes calldata seal,
+        bytes calldata journal,
+        Eip712RingSignatureData memory _ringSignatureData
+    ) external view returns (Eip712RingSignatureData memory);
+
+    // verify a lsag over a large ring, proven in chunks
+    function verifyChunkedRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        RingSignatureData memory _ringSignatureData
+    ) external view returns (RingSignatureData memory);
+
+    // verify a lsag over a large ring and a binary message, proven in chunks
+    function verifyChunkedBytesRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        BytesRingSignatureData memory _ringSignatureData
+    ) external view returns (BytesRingSignatureData memory);
+
+    // verify a lsag over a large ring given only the sha256 digest of its message, proven in chunks
+    function verifyChunkedPrehashedRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        PrehashedRingSignatureData memory _ringSignatureData
+    ) external view returns (PrehashedRingSignatureData memory);
+
+    // verify a lsag over a large ring and an EIP-191 personal message, proven in chunks
+    function verifyChunkedEip191Rs(
+        bytes calldata seal,
+        bytes calldata journal,
+        Eip191RingSignatureData memory _ringSignatureData
+    ) external view returns (Eip191RingSignatureData memory);
+
+    // verify a lsag over a large ring and an EIP-712 typed data payload, proven in chunks
+    function verifyChunkedEip712Rs(
+        bytes calldata seal,
+        bytes calldata journal,
+        Eip712RingSignatureData memory _ringSignatureData
+    ) external view returns (Eip712RingSignatureData memory);
+
+    // verify a lsag endorsing the journal of another receipt, returns the endorsed image id
+    function verifyEndorsementRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        BytesRingSignatureData memory _ringSignatureData
+    ) external view returns (bytes32, BytesRingSignatureData memory);
+
+    // prove whether a base64 encoded lsag is valid, returns the status code and the lsag digest
+    function verifyStatus(bytes calldata seal, bytes calldata journal, string calldata signature)
+        external
+        view
+        returns (uint8, bytes32);
+
+    // verify a batch of lsag proven independently, returns the digest of each ring signature data
+    function verifyAggregatedRs(bytes calldata seal, bytes calldata journal)
+        external
+        view
+        returns (bytes32[] memory);
 }
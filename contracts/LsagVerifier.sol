@@ -7,6 +7,20 @@ import {ImageID} from "./ImageID.sol"; // auto-generated contract after running
 contract LsagVerifier {
     IRiscZeroVerifier public immutable verifier;
     bytes32 public constant imageId = ImageID.LSAG_VERIFIER_ID;
+    bytes32 public constant aggregatorImageId = ImageID.LSAG_AGGREGATOR_ID;
+    bytes32 public constant chunkImageId = ImageID.LSAG_CHUNK_ID;
+    bytes32 public constant chunkedVerifierImageId = ImageID.LSAG_CHUNKED_VERIFIER_ID;
+    bytes32 public constant endorsementImageId = ImageID.LSAG_ENDORSEMENT_ID;
+    bytes32 public constant statusImageId = ImageID.LSAG_STATUS_ID;
+
+    // status codes committed by the status guest
+    uint8 public constant STATUS_VALID = 0;
+    uint8 public constant STATUS_MALFORMED_INPUT = 1;
+    uint8 public constant STATUS_INVALID_POINT = 2;
+    uint8 public constant STATUS_INVALID_SCALAR = 3;
+    uint8 public constant STATUS_INVALID_MESSAGE = 4;
+    uint8 public constant STATUS_RING_SIZE_MISMATCH = 5;
+    uint8 public constant STATUS_INVALID_SIGNATURE = 6;
 
     struct Point {
         uint256 x;
@@ -20,6 +34,36 @@ contract LsagVerifier {
         Point[] ring;
     }
 
+    struct BytesRingSignatureData {
+        bytes message;
+        string linkabilityFlag;
+        Point keyImage;
+        Point[] ring;
+    }
+
+    struct PrehashedRingSignatureData {
+        bytes32 messageDigest;
+        string linkabilityFlag;
+        Point keyImage;
+        Point[] ring;
+    }
+
+    struct Eip191RingSignatureData {
+        bytes32 messageDigest;
+        bytes message;
+        string linkabilityFlag;
+        Point keyImage;
+        Point[] ring;
+    }
+
+    struct Eip712RingSignatureData {
+        bytes32 domainSeparator;
+        bytes32 structHash;
+        string linkabilityFlag;
+        Point keyImage;
+        Point[] ring;
+    }
+
     constructor(IRiscZeroVerifier _verifierAddress) {
         verifier = _verifierAddress;
     }
@@ -35,14 +79,203 @@ contract LsagVerifier {
         bytes calldata journal,
         RingSignatureData memory _ringSignatureData
     ) external view returns (RingSignatureData memory) {
+        _verifyJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // verify a linkable ring signature over a binary message
+    // Same as verifyRs, for signatures whose message is arbitrary bytes (hex or base64 encoded in the guest input)
+    // string and bytes share the same abi encoding, so the journal is computed the same way
+    function verifyBytesRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        BytesRingSignatureData memory _ringSignatureData
+    ) external view returns (BytesRingSignatureData memory) {
+        _verifyJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // verify a linkable ring signature given only the sha256 digest of its message
+    // The guest was given the digest instead of the message, so the journal only binds the digest:
+    // the message itself never needs to be sent to the guest nor to this contract
+    function verifyPrehashedRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        PrehashedRingSignatureData memory _ringSignatureData
+    ) external view returns (PrehashedRingSignatureData memory) {
+        _verifyJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // verify a linkable ring signature over an EIP-191 personal message
+    // The signed digest is messageDigest = keccak256("\x19Ethereum Signed Message:\n" || len(message) || message),
+    // computed by the guest from message
+    function verifyEip191Rs(
+        bytes calldata seal,
+        bytes calldata journal,
+        Eip191RingSignatureData memory _ringSignatureData
+    ) external view returns (Eip191RingSignatureData memory) {
+        _verifyJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // verify a linkable ring signature over an EIP-712 typed data payload
+    // The signed digest is keccak256("\x19\x01" || domainSeparator || structHash), computed by the guest from the typed data
+    // Callers should check domainSeparator against their own EIP-712 domain, and recompute structHash from the expected intent
+    function verifyEip712Rs(
+        bytes calldata seal,
+        bytes calldata journal,
+        Eip712RingSignatureData memory _ringSignatureData
+    ) external view returns (Eip712RingSignatureData memory) {
+        _verifyJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // verify a linkable ring signature endorsing the output of another risc zero computation
+    // the signed message is the sha256 digest of the endorsed receipt's journal, which the guest verified
+    // the journal is composed as the following :
+    // journal : endorsedImageId || sha256(_ringSignatureData)
+    // it returns the image id of the endorsed computation, callers must check it is the expected one,
+    // and _ringSignatureData.message is the digest of the endorsed journal
+    function verifyEndorsementRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        BytesRingSignatureData memory _ringSignatureData
+    ) external view returns (bytes32, BytesRingSignatureData memory) {
+        verifier.verify(seal, endorsementImageId, sha256(journal));
+        if (bytes32(journal[32:64]) != sha256(abi.encode(_ringSignatureData))) {
+            revert("Journal hash and ring signature data digest doesn't match");
+        }
+        //the data is trusted, you can implem your own logic here
+        return (bytes32(journal[0:32]), _ringSignatureData);
+    }
+
+    // prove the outcome of the verification of a base64 encoded signature, valid or not
+    // the status guest never panics on an invalid signature, the journal is composed as the following :
+    // journal : abi.encode(status, sha256(signature), sha256(_ringSignatureData) or 0 if invalid)
+    // a status other than STATUS_VALID proves that the signature is invalid, e.g. to penalize whoever published it
+    function verifyStatus(bytes calldata seal, bytes calldata journal, string calldata signature)
+        external
+        view
+        returns (uint8, bytes32)
+    {
+        verifier.verify(seal, statusImageId, sha256(journal));
+        (uint8 status, bytes32 inputDigest, bytes32 lsagDigest) = abi.decode(journal, (uint8, bytes32, bytes32));
+        //ensure that the status is the one of the given signature
+        if (inputDigest != sha256(bytes(signature))) {
+            revert("Journal input digest and signature digest doesn't match");
+        }
+        return (status, lsagDigest);
+    }
+
+    // verify a batch of linkable ring signatures proven independently and aggregated in a single proof
+    // the aggregator guest verified one lsag_verifier receipt per signature, the journal is composed as the following :
+    // journal : abi.encode(imageId, [sha256(_ringSignatureData), ...])
+    // it returns the digests, a ring signature data belongs to the batch if sha256(abi.encode(data)) is one of them
+    function verifyAggregatedRs(bytes calldata seal, bytes calldata journal)
+        external
+        view
+        returns (bytes32[] memory)
+    {
+        verifier.verify(seal, aggregatorImageId, sha256(journal));
+        (bytes32 verifierImageId, bytes32[] memory digests) = abi.decode(journal, (bytes32, bytes32[]));
+        //ensure that the aggregated receipts were produced by the lsag verifier guest
+        if (verifierImageId != imageId) {
+            revert("Aggregated receipts were not produced by the lsag verifier");
+        }
+        return digests;
+    }
+
+    // verify a linkable ring signature over a large ring, whose challenge chain was proven in chunks
+    // the chunked verifier guest composed one lsag_chunk receipt per chunk, the journal is composed as the following :
+    // journal : chunkImageId || sha256(_ringSignatureData)
+    // if the signature is valid, it returns the ringSignature data passed as argument
+    function verifyChunkedRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        RingSignatureData memory _ringSignatureData
+    ) external view returns (RingSignatureData memory) {
+        _verifyChunkedJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // same as verifyChunkedRs, for a binary message
+    function verifyChunkedBytesRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        BytesRingSignatureData memory _ringSignatureData
+    ) external view returns (BytesRingSignatureData memory) {
+        _verifyChunkedJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // same as verifyChunkedRs, given only the sha256 digest of the message
+    function verifyChunkedPrehashedRs(
+        bytes calldata seal,
+        bytes calldata journal,
+        PrehashedRingSignatureData memory _ringSignatureData
+    ) external view returns (PrehashedRingSignatureData memory) {
+        _verifyChunkedJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // same as verifyChunkedRs, for an EIP-191 personal message
+    function verifyChunkedEip191Rs(
+        bytes calldata seal,
+        bytes calldata journal,
+        Eip191RingSignatureData memory _ringSignatureData
+    ) external view returns (Eip191RingSignatureData memory) {
+        _verifyChunkedJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // same as verifyChunkedRs, for an EIP-712 typed data payload
+    function verifyChunkedEip712Rs(
+        bytes calldata seal,
+        bytes calldata journal,
+        Eip712RingSignatureData memory _ringSignatureData
+    ) external view returns (Eip712RingSignatureData memory) {
+        _verifyChunkedJournal(seal, journal, abi.encode(_ringSignatureData));
+        //the data is trusted, you can implem your own logic here
+        return _ringSignatureData;
+    }
+
+    // check the proof and ensure that the journal commits to the abi encoded ring signature data
+    function _verifyJournal(bytes calldata seal, bytes calldata journal, bytes memory encodedRingSignatureData)
+        internal
+        view
+    {
         verifier.verify(seal, imageId, sha256(journal));
         //decode the journal
         bytes32 hash_ring_sig_data = bytes32(journal[0:32]);
         //ensure that the ring signature data are the one that have been used on risc zero
-        if (hash_ring_sig_data != sha256(abi.encode(_ringSignatureData))) {
+        if (hash_ring_sig_data != sha256(encodedRingSignatureData)) {
+            revert("Journal hash and ring signature data digest doesn't match");
+        }
+    }
+
+    // check the chunked verifier proof, and ensure that the chunks were proven by the lsag chunk guest
+    // and that the journal commits to the abi encoded ring signature data
+    function _verifyChunkedJournal(bytes calldata seal, bytes calldata journal, bytes memory encodedRingSignatureData)
+        internal
+        view
+    {
+        verifier.verify(seal, chunkedVerifierImageId, sha256(journal));
+        //ensure that the chunks were proven by the lsag chunk guest
+        if (bytes32(journal[0:32]) != chunkImageId) {
+            revert("Chunks were not proven by the lsag chunk guest");
+        }
+        if (bytes32(journal[32:64]) != sha256(encodedRingSignatureData)) {
             revert("Journal hash and ring signature data digest doesn't match");
         }
-        //the data is trusted, you can implem your own logic here
-        return _ringSignatureData;
     }
 }
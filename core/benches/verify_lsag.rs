@@ -0,0 +1,54 @@
+// Benchmarks the native verification of ring signatures of growing ring sizes.
+//
+//     cargo bench -p lsag-core
+//
+// Criterion writes its estimates as JSON under target/criterion/verify_lsag/<ring size>/, and
+// `-- --save-baseline <name>` / `-- --baseline <name>` compare two versions.
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use k256::Scalar;
+use lsag_core::lsag_verifier::conversion::convert_string_to_json;
+use lsag_core::lsag_verifier::lsag_verifier::verify_lsag;
+use lsag_core::lsag_verifier::signer::sample_b64_lsag;
+use lsag_core::utils::{decode_ring, scalar_from_hex};
+
+const RING_SIZES: [usize; 6] = [2, 8, 32, 128, 512, 1024];
+
+fn bench_verify_lsag(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("verify_lsag");
+    group.sample_size(10);
+    for ring_size in RING_SIZES {
+        let b64 = sample_b64_lsag(ring_size, 0).unwrap();
+        let json = general_purpose::STANDARD.decode(b64).unwrap();
+        let lsag = convert_string_to_json(std::str::from_utf8(&json).unwrap()).unwrap();
+        let ring = decode_ring(&lsag.ring).unwrap();
+        let c0 = scalar_from_hex(&lsag.c).unwrap();
+        let responses = lsag
+            .responses
+            .iter()
+            .map(|response| scalar_from_hex(response))
+            .collect::<Result<Vec<Scalar>, String>>()
+            .unwrap();
+        let key_image = lsag.keyImage.decode().unwrap();
+        let message = lsag.message.unwrap();
+
+        group.bench_with_input(BenchmarkId::from_parameter(ring_size), &ring, |b, ring| {
+            b.iter(|| {
+                assert!(verify_lsag(
+                    black_box(ring),
+                    message.as_bytes(),
+                    c0,
+                    &responses,
+                    key_image,
+                    Some(&lsag.linkabilityFlag),
+                ))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_verify_lsag);
+criterion_main!(benches);
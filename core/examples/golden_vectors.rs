@@ -0,0 +1,95 @@
+// Writes the extra golden test vectors signed by the lsag-core signer to
+// core/tests/vectors/self_signed/, and fills in the expected digest of the vectors of the
+// reference signing library in core/tests/vectors/reference/ that have none yet.
+//
+//     cargo run -p lsag-core --example golden_vectors
+//
+// The self-signed signatures are derived from fixed seeds, so the files only change when the
+// signer or the minimal LSAG encoding changes. The reference vectors are signed by
+// core/tests/vectors/reference/generate.mjs.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use lsag_core::lsag_verifier::conversion::convert_string_to_json;
+use lsag_core::lsag_verifier::minimal::to_minimal_lsag_digest;
+use lsag_core::lsag_verifier::signer::sample_b64_lsag_with_message;
+use lsag_core::utils::decode_ring;
+use serde_json::{json, Value};
+
+/// (file name, message, linkability flag, ring size, seed)
+const VECTORS: [(&str, &str, &str, usize, u64); 7] = [
+    ("ring_1", "Hello World", "linkability", 1, 1),
+    ("ring_2_empty_message", "", "linkability", 2, 2),
+    ("ring_3_empty_flag", "vote:2026-10-19", "", 3, 3),
+    ("ring_5_unicode_message", "héllo wörld ✓", "poll-42", 5, 5),
+    ("ring_8_long_message", LOREM, "linkability", 8, 8),
+    (
+        "ring_12_flag_with_spaces",
+        "Hello World",
+        "a flag with spaces",
+        12,
+        12,
+    ),
+    ("ring_16", "Hello World", "linkability", 16, 16),
+];
+
+const LOREM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+fn main() {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+    for (name, message, linkability_flag, ring_size, seed) in VECTORS {
+        let signature =
+            sample_b64_lsag_with_message(message, linkability_flag, ring_size, seed).unwrap();
+        let vector = json!({
+            "description": format!(
+                "self-signed: ring of {} members, {} byte message, flag {:?}",
+                ring_size,
+                message.len(),
+                linkability_flag
+            ),
+            "signer": "lsag-core",
+            "minimal_lsag_digest": hex::encode(minimal_lsag_digest(&signature)),
+            "signature": signature,
+        });
+        write(
+            &directory.join(format!("self_signed/{}.json", name)),
+            &vector,
+        );
+    }
+
+    for entry in fs::read_dir(directory.join("reference")).unwrap() {
+        let path = entry.unwrap().path();
+        if path
+            .extension()
+            .map_or(true, |extension| extension != "json")
+        {
+            continue;
+        }
+        let mut vector: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        if vector.get("minimal_lsag_digest").is_none() {
+            let digest = minimal_lsag_digest(vector["signature"].as_str().unwrap());
+            vector["minimal_lsag_digest"] = Value::String(hex::encode(digest));
+            write(&path, &vector);
+        }
+    }
+}
+
+/// The expected digest, computed from the decoded fields rather than by the verifier.
+fn minimal_lsag_digest(signature: &str) -> [u8; 32] {
+    let json = general_purpose::STANDARD.decode(signature).unwrap();
+    let lsag = convert_string_to_json(std::str::from_utf8(&json).unwrap()).unwrap();
+    to_minimal_lsag_digest(
+        &decode_ring(&lsag.ring).unwrap(),
+        lsag.message.unwrap().as_bytes(),
+        lsag.keyImage.decode().unwrap(),
+        Some(&lsag.linkabilityFlag),
+    )
+}
+
+fn write(path: &Path, vector: &Value) {
+    fs::write(path, serde_json::to_string_pretty(vector).unwrap() + "\n").unwrap();
+    println!("wrote {}", path.display());
+}
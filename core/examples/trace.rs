@@ -0,0 +1,28 @@
+// Prints the trace of the challenge chain of a base64-encoded LSAG, natively.
+//
+//     cargo run --example trace -- <BASE64_SIGNATURE>
+//
+// The signature is read from stdin when not given as argument.
+
+use lsag_core::lsag_verifier::trace_b64_lsag;
+use std::io::Read;
+
+fn main() {
+    let input = match std::env::args().nth(1) {
+        Some(input) => input,
+        None => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .expect("failed to read stdin");
+            input
+        }
+    };
+    match trace_b64_lsag(input.trim()) {
+        Ok(trace) => println!("{}", serde_json::to_string_pretty(&trace).unwrap()),
+        Err(e) => {
+            eprintln!("LSAG trace failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lsag_core::utils::{deserialize_point, serialize_point};
+
+// Accepted points must survive a round trip through their compressed serialization.
+fuzz_target!(|data: &str| {
+    if let Ok(point) = deserialize_point(data) {
+        let serialized = serialize_point(point).expect("a decoded point serializes");
+        assert_eq!(deserialize_point(&serialized), Ok(point));
+    }
+});
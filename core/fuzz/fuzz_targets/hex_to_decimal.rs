@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lsag_core::utils::hex_to_decimal;
+
+fuzz_target!(|data: &str| {
+    if let Ok(decimal) = hex_to_decimal(data) {
+        assert!(!decimal.is_empty() && decimal.bytes().all(|b| b.is_ascii_digit()));
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use libfuzzer_sys::fuzz_target;
+use lsag_core::lsag_verifier::try_verify_b64_lsag;
+
+// The whole JSON input, past the base64 decoding: message formats, point encodings, scalars.
+fuzz_target!(|json: &str| {
+    let _ = try_verify_b64_lsag(&general_purpose::STANDARD.encode(json));
+});
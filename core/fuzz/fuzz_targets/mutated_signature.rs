@@ -0,0 +1,169 @@
+#![no_main]
+
+// Structured fuzzing of valid signatures: the input picks one of the seed signatures and a
+// list of mutations of its fields. A mutated signature may only verify if it is another
+// encoding of the same signature: the same ring, message digest, flag and scalars, hence the
+// same trace of the challenge chain.
+
+use std::sync::OnceLock;
+
+use arbitrary::Arbitrary;
+use base64::engine::general_purpose;
+use base64::Engine;
+use libfuzzer_sys::fuzz_target;
+use lsag_core::lsag_verifier::lsag_verifier::LsagTrace;
+use lsag_core::lsag_verifier::{trace_b64_lsag, try_verify_b64_lsag};
+use serde_json::Value;
+
+const SEEDS: [&str; 5] = [
+    include_str!("../seeds/verify_b64_lsag/demo"),
+    include_str!("../seeds/verify_b64_lsag/demo_hex_message"),
+    include_str!("../seeds/verify_b64_lsag/demo_prehashed"),
+    include_str!("../seeds/verify_b64_lsag/demo_uncompressed"),
+    include_str!("../seeds/verify_b64_lsag/demo_coordinates"),
+];
+
+#[derive(Arbitrary, Debug)]
+enum Field {
+    C,
+    Response(u8),
+    RingMember(u8),
+    KeyImage,
+    Message,
+    MessageDigest,
+    MessageEncoding,
+    MessageFormat,
+    LinkabilityFlag,
+}
+
+#[derive(Arbitrary, Debug)]
+enum Mutation {
+    FlipBit(Field, u16),
+    Replace(Field, String),
+    Remove(Field),
+    SwapRingMembers(u8, u8),
+    DuplicateRingMember(u8),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    seed: u8,
+    mutations: Vec<Mutation>,
+}
+
+/// The decoded JSON and the trace of each seed.
+fn seeds() -> &'static Vec<(Value, LsagTrace)> {
+    static DECODED_SEEDS: OnceLock<Vec<(Value, LsagTrace)>> = OnceLock::new();
+    DECODED_SEEDS.get_or_init(|| {
+        SEEDS
+            .iter()
+            .map(|seed| {
+                let json = general_purpose::STANDARD.decode(seed.trim()).unwrap();
+                let trace = trace_b64_lsag(seed.trim()).unwrap();
+                assert!(trace.valid, "seeds are valid signatures");
+                (serde_json::from_slice(&json).unwrap(), trace)
+            })
+            .collect()
+    })
+}
+
+/// The JSON value of `field`, or `None` when it is absent.
+fn field_mut<'a>(json: &'a mut Value, field: &Field) -> Option<&'a mut Value> {
+    fn element(array: &mut Value, index: u8) -> Option<&mut Value> {
+        let array = array.as_array_mut()?;
+        let len = array.len();
+        array.get_mut(index as usize % len.max(1))
+    }
+    match field {
+        Field::C => json.get_mut("c"),
+        Field::Response(index) => element(json.get_mut("responses")?, *index),
+        Field::RingMember(index) => element(json.get_mut("ring")?, *index),
+        Field::KeyImage => json.get_mut("keyImage"),
+        Field::Message => json.get_mut("message"),
+        Field::MessageDigest => json.get_mut("messageDigest"),
+        Field::MessageEncoding => json.get_mut("messageEncoding"),
+        Field::MessageFormat => json.get_mut("messageFormat"),
+        Field::LinkabilityFlag => json.get_mut("linkabilityFlag"),
+    }
+}
+
+fn field_name(field: &Field) -> &'static str {
+    match field {
+        Field::C => "c",
+        Field::Response(_) => "responses",
+        Field::RingMember(_) => "ring",
+        Field::KeyImage => "keyImage",
+        Field::Message => "message",
+        Field::MessageDigest => "messageDigest",
+        Field::MessageEncoding => "messageEncoding",
+        Field::MessageFormat => "messageFormat",
+        Field::LinkabilityFlag => "linkabilityFlag",
+    }
+}
+
+fn mutate(json: &mut Value, mutation: &Mutation) {
+    match mutation {
+        Mutation::FlipBit(field, bit) => {
+            if let Some(Value::String(value)) = field_mut(json, field) {
+                let mut bytes = value.clone().into_bytes();
+                if !bytes.is_empty() {
+                    let bit = *bit as usize % (bytes.len() * 8);
+                    bytes[bit / 8] ^= 1 << (bit % 8);
+                    *value = String::from_utf8_lossy(&bytes).into_owned();
+                }
+            }
+        }
+        Mutation::Replace(field, replacement) => match field_mut(json, field) {
+            Some(value) => *value = Value::String(replacement.clone()),
+            None => json[field_name(field)] = Value::String(replacement.clone()),
+        },
+        Mutation::Remove(field) => {
+            let index = match field {
+                Field::Response(index) | Field::RingMember(index) => Some(*index),
+                _ => None,
+            };
+            match (json.get_mut(field_name(field)), index) {
+                (Some(Value::Array(array)), Some(index)) if !array.is_empty() => {
+                    let len = array.len();
+                    array.remove(index as usize % len);
+                }
+                _ => {
+                    json.as_object_mut().unwrap().remove(field_name(field));
+                }
+            }
+        }
+        Mutation::SwapRingMembers(a, b) => {
+            if let Some(Value::Array(ring)) = json.get_mut("ring") {
+                if !ring.is_empty() {
+                    let len = ring.len();
+                    ring.swap(*a as usize % len, *b as usize % len);
+                }
+            }
+        }
+        Mutation::DuplicateRingMember(index) => {
+            if let Some(Value::Array(ring)) = json.get_mut("ring") {
+                if let Some(member) = ring.get(*index as usize % ring.len().max(1)).cloned() {
+                    ring.push(member);
+                }
+            }
+        }
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let seeds = seeds();
+    let (seed, trace) = &seeds[input.seed as usize % seeds.len()];
+    let mut json = seed.clone();
+    for mutation in &input.mutations {
+        mutate(&mut json, mutation);
+    }
+    let b64 = general_purpose::STANDARD.encode(json.to_string());
+    if try_verify_b64_lsag(&b64).is_ok() {
+        assert_eq!(
+            trace_b64_lsag(&b64).as_ref(),
+            Ok(trace),
+            "a mutated signature verifies: {}",
+            json
+        );
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lsag_core::utils::scalar_from_hex;
+use lsag_core::utils::scalar_to_string::scalar_to_string;
+
+// Accepted scalars must survive a round trip through their hex serialization.
+fuzz_target!(|data: &str| {
+    if let Ok(scalar) = scalar_from_hex(data) {
+        assert_eq!(scalar_from_hex(&scalar_to_string(&scalar)), Ok(scalar));
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use lsag_core::lsag_verifier::{try_verify_b64_lsag, verify_b64_lsag};
+
+// Any input, base64 or not, must be rejected or verified without panicking.
+fuzz_target!(|data: &str| {
+    let verified = verify_b64_lsag(data.to_string());
+    assert_eq!(verified, try_verify_b64_lsag(data).ok());
+});
@@ -0,0 +1,32 @@
+use ethabi::{encode, Token};
+
+// ABI encode the journal of the aggregator guest, matching
+// `abi.encode(bytes32 verifierImageId, bytes32[] digests)` on chain
+pub fn abi_encode_aggregation(verifier_image_id: &[u8; 32], digests: &[[u8; 32]]) -> Vec<u8> {
+    let digests = digests
+        .iter()
+        .map(|digest| Token::FixedBytes(digest.to_vec()))
+        .collect();
+    encode(&[
+        Token::FixedBytes(verifier_image_id.to_vec()),
+        Token::Array(digests),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_image_id_then_digest_array() {
+        let encoded = abi_encode_aggregation(&[0xaa; 32], &[[0x01; 32], [0x02; 32]]);
+        let words: Vec<&[u8]> = encoded.chunks(32).collect();
+        assert_eq!(words.len(), 5);
+        assert_eq!(words[0], [0xaa; 32]);
+        // offset of the dynamic array, then its length
+        assert_eq!(words[1][31], 0x40);
+        assert_eq!(words[2][31], 2);
+        assert_eq!(words[3], [0x01; 32]);
+        assert_eq!(words[4], [0x02; 32]);
+    }
+}
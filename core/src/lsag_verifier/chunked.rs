@@ -0,0 +1,221 @@
+use crate::lsag_verifier::compute::compute_chain;
+use crate::lsag_verifier::lsag_verifier::{decode_b64_lsag, resolve_message};
+use crate::lsag_verifier::minimal::{digest_minimal_lsag, MinimalLsag};
+use crate::utils::{decode_ring, scalar_from_hex, serialize_point, serialize_ring, PointEncoding};
+use k256::elliptic_curve::PrimeField;
+use k256::{AffinePoint, Scalar};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The journal of a chunk of the challenge chain.
+///
+/// It proves that, starting from `c_start` at index `start`, the chain reaches `c_end` at
+/// index `end` (`end == ring_size` standing for the closing challenge `c0`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChunkJournal {
+    /// sha256 digest of the serialized ring
+    pub ring_commitment: [u8; 32],
+    /// sha256 digest of the message digest, serialized key image and linkability flag
+    pub context: [u8; 32],
+    pub ring_size: u32,
+    pub start: u32,
+    pub end: u32,
+    /// big endian challenges
+    pub c_start: [u8; 32],
+    pub c_end: [u8; 32],
+}
+
+/// Walks the challenge chain of a base64-encoded LSAG over the ring members `start..start + len`.
+///
+/// Only the members of the chunk are decoded, the others are hashed as given when already
+/// serialized, so that each chunk costs little more than the ring commitment.
+/// The chunk starts from `c_start`, or from the signature's `c0` when `None`.
+pub fn verify_chunk(
+    b64_signature: &str,
+    start: u32,
+    len: u32,
+    c_start: Option<[u8; 32]>,
+) -> Result<ChunkJournal, String> {
+    let json = decode_b64_lsag(b64_signature).ok_or("Invalid signature encoding")?;
+    let ring_size = json.ring.len();
+    if json.responses.len() != ring_size {
+        return Err("Expected one response per ring member".to_string());
+    }
+    let start_index = start as usize;
+    let end_index = start_index.saturating_add(len as usize).min(ring_size);
+    if start_index >= end_index {
+        return Err("Empty chunk".to_string());
+    }
+
+    let serialized_ring = serialize_encoded_ring(&json.ring)?;
+    let members = decode_ring(&json.ring[start_index..end_index])?;
+    let responses = json.responses[start_index..end_index]
+        .iter()
+        .map(|response| scalar_from_hex(response))
+        .collect::<Result<Vec<Scalar>, String>>()?;
+    let key_image = json.keyImage.decode()?;
+    let (message_digest, _) = resolve_message(&json).ok_or("Invalid message")?;
+    let linkability_flag = Some(json.linkabilityFlag.as_str());
+
+    let c_start = match c_start {
+        Some(bytes) => Option::<Scalar>::from(Scalar::from_repr(bytes.into()))
+            .ok_or("Non canonical challenge")?,
+        None => scalar_from_hex(&json.c)?,
+    };
+    let ring_hasher = Sha256::new_with_prefix(serialized_ring.as_bytes());
+    let c_end = compute_chain(
+        &members,
+        &ring_hasher,
+        &hex::encode(message_digest),
+        c_start,
+        &responses,
+        key_image,
+        linkability_flag,
+    )?;
+
+    Ok(ChunkJournal {
+        ring_commitment: Sha256::digest(serialized_ring.as_bytes()).into(),
+        context: chain_context(&message_digest, key_image, &json.linkabilityFlag)?,
+        ring_size: ring_size as u32,
+        start,
+        end: end_index as u32,
+        c_start: c_start.to_bytes().into(),
+        c_end: c_end.to_bytes().into(),
+    })
+}
+
+/// Verifies a base64-encoded LSAG from the journals of the chunks of its challenge chain.
+///
+/// The chunks must share the ring and context of the signature, follow each other from index 0
+/// and `c0` to the end of the ring, and close the chain at `c0`. The receipts of the chunks are
+/// checked by the caller.
+///
+/// # Returns
+/// * `Some([u8; 32])` - The minimal LSAG digest, as committed by `verify_b64_lsag`, if the chain closes.
+/// * `None` - Otherwise.
+pub fn verify_chunked_b64_lsag(b64_signature: &str, chunks: &[ChunkJournal]) -> Option<[u8; 32]> {
+    let json = decode_b64_lsag(b64_signature)?;
+    let ring = decode_ring(&json.ring).ok()?;
+    let key_image = json.keyImage.decode().ok()?;
+    let (message_digest, message) = resolve_message(&json)?;
+    let c0: [u8; 32] = scalar_from_hex(&json.c).ok()?.to_bytes().into();
+
+    let ring_commitment: [u8; 32] = Sha256::digest(serialize_ring(&ring).ok()?.as_bytes()).into();
+    let context = chain_context(&message_digest, key_image, &json.linkabilityFlag).ok()?;
+    let mut next_index = 0;
+    let mut next_c = c0;
+    for chunk in chunks {
+        if chunk.ring_commitment != ring_commitment
+            || chunk.context != context
+            || chunk.ring_size as usize != ring.len()
+            || chunk.start != next_index
+            || chunk.end <= chunk.start
+            || chunk.c_start != next_c
+        {
+            return None;
+        }
+        next_index = chunk.end;
+        next_c = chunk.c_end;
+    }
+    if ring.is_empty() || next_index as usize != ring.len() || next_c != c0 {
+        return None;
+    }
+
+    Some(digest_minimal_lsag(&MinimalLsag {
+        message,
+        key_image,
+        linkability_flag: Some(json.linkabilityFlag.as_str()),
+        ring,
+    }))
+}
+
+/// Serializes the ring as `serialize_ring` would, reusing the members already given in
+/// compressed form instead of decoding them.
+fn serialize_encoded_ring(ring: &[PointEncoding]) -> Result<String, String> {
+    let mut serialized = String::with_capacity(ring.len() * 66);
+    for point in ring {
+        match point {
+            PointEncoding::Hex(hex_str) if is_serialized_point(hex_str) => {
+                serialized.push_str(hex_str)
+            }
+            _ => serialized.push_str(&serialize_point(point.decode()?)?),
+        }
+    }
+    Ok(serialized)
+}
+
+/// Whether `hex_str` is in the exact format output by `serialize_point`.
+/// It may still not be a point: the verification of the whole chain decodes every member.
+fn is_serialized_point(hex_str: &str) -> bool {
+    hex_str.len() == 66
+        && (hex_str.starts_with("02") || hex_str.starts_with("03"))
+        && hex_str
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+fn chain_context(
+    message_digest: &[u8; 32],
+    key_image: AffinePoint,
+    linkability_flag: &str,
+) -> Result<[u8; 32], String> {
+    let mut hasher = Sha256::new();
+    hasher.update(message_digest);
+    hasher.update(serialize_point(key_image)?.as_bytes());
+    hasher.update(linkability_flag.as_bytes());
+    Ok(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsag_verifier::verify_b64_lsag;
+
+    // publisher demo signature, over a ring of 4 members
+    const SIGNATURE: &str = "eyJtZXNzYWdlIjoiSGVsbG8gV29ybGQiLCJyaW5nIjpbIjAyMjE4NjljYTNhZTMzYmUzYTczMjdlOWEwMjcyMjAzYWZhNzJjNTJhNTQ2MGNlYjlmNGE1MDkzMDUzMWJkOTI2YSIsIjAzNDI4ZTAyMGYxODRiNzBjYTkzMWE5MTA4NWFjMWMyMzM4MjdhNDFkODUxNmE0YjY0NTVlMjIxZTYzN2M0ZGUwZiIsIjAzNDM4ZmJmMzc3NmNjMjRlMjUzNTgyMjU0NGYxNGQ3YjA1N2Q4OTU3YzcyNjc2MDE4MDA1MmNkYTdiOGJhNmM4MyIsIjAzNTBjMWJkNjRjMzA4N2Y2NWY0ODE3MTdlZTRhNWJkZmJiYTRmMDYwMzE0OTkzZjFlMTVjMGRiMjk3NDhiOGRjMiJdLCJjIjoiM2M3ZDBhYzE4YjBlYWU4N2M1OTFjMGM5ZWRkOWE3ZDU3YjI5ZWUxZDhiNzZlYTFjOGM1NjAxMDQ3MGMwZDViMiIsInJlc3BvbnNlcyI6WyIxOWUzNGNjOTc5Y2E1YWMzYTk2MThkMGNlZThmYjdmNzRlMmY4MzA5MmY2ZDZmOTUyZTA3OWYxMzY1MmNlNjM2IiwiYjRkZGE5ZTc4YzA4OTliYjFjYmNkYTVjMjhiYWRiZjYwYWIzMDc1N2MyZjVhMWIxNWQwZDliNmQ1MzdhMTMwMSIsIjJhY2Q4ZWIzMzZhZjU5YzIwMTVhNDljMGJlMWZhZmE3Yzk0ODRmYWQ4YmY3MmFmYjZjYmIwYzgzMDhhOGUxODUiLCI1Y2IzNWY3OWVmYzBmODEwYTI0NTMxYjU0YWM0NThiNjZkMTZlNzNhMTdjOWEyY2IxYTkyN2QzYzI1YTNkMDY4Il0sImN1cnZlIjoie1wiY3VydmVcIjpcIlNFQ1AyNTZLMVwifSIsImtleUltYWdlIjoiMDJlN2ZmMzQ5MGVlN2RiMzM3NTBmZTlhNzA5MWQ4MmRjYTk1MmU2ZDIyYTdmZDRkZjk3ZDBjYmY4ZjdjYjQ2YWQyIiwibGlua2FiaWxpdHlGbGFnIjoibGlua2FiaWxpdHkiLCJjb25maWciOnsiaGFzaCI6InNoYTI1NiJ9fQ==";
+
+    fn chunks(len: u32) -> Vec<ChunkJournal> {
+        let mut chunks: Vec<ChunkJournal> = Vec::new();
+        loop {
+            let previous = chunks.last();
+            let chunk = verify_chunk(
+                SIGNATURE,
+                previous.map_or(0, |chunk| chunk.end),
+                len,
+                previous.map(|chunk| chunk.c_end),
+            )
+            .unwrap();
+            let done = chunk.end == chunk.ring_size;
+            chunks.push(chunk);
+            if done {
+                return chunks;
+            }
+        }
+    }
+
+    #[test]
+    fn chunked_chain_matches_the_full_verification() {
+        let expected = verify_b64_lsag(SIGNATURE.to_string()).unwrap();
+        for len in 1..=4 {
+            let chunks = chunks(len);
+            assert_eq!(chunks.len(), 4usize.div_ceil(len as usize));
+            assert_eq!(verify_chunked_b64_lsag(SIGNATURE, &chunks), Some(expected));
+        }
+    }
+
+    #[test]
+    fn rejects_incomplete_or_tampered_chains() {
+        let chunks = chunks(2);
+        assert!(verify_chunked_b64_lsag(SIGNATURE, &chunks[..1]).is_none());
+        assert!(
+            verify_chunked_b64_lsag(SIGNATURE, &[chunks[1].clone(), chunks[0].clone()]).is_none()
+        );
+        let mut tampered = chunks.clone();
+        tampered[1].c_start[31] ^= 1;
+        assert!(verify_chunked_b64_lsag(SIGNATURE, &tampered).is_none());
+        let mut tampered = chunks;
+        tampered[0].context[0] ^= 1;
+        assert!(verify_chunked_b64_lsag(SIGNATURE, &tampered).is_none());
+        assert!(verify_chunk(SIGNATURE, 4, 1, None).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::utils::{hash_to_secp256k1, hex_to_decimal, scalar_from_hex, serialize_point};
+use k256::{AffinePoint, Scalar};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+pub struct Params<'a> {
+    pub index: usize,
+    pub previous_r: Scalar,
+    pub previous_c: Scalar,
+    pub previous_index: usize,
+    pub key_image: AffinePoint,
+    pub linkability_flag: Option<&'a str>,
+}
+
+/// The intermediate values of the computation of a challenge, to diagnose signer mismatches.
+/// Points are serialized as in the hash inputs.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChallengeTrace {
+    /// Index of the ring member whose response is used
+    pub index: usize,
+    /// The ring member
+    pub serialized_point: String,
+    /// The ring member concatenated with the linkability flag, mapped to the curve
+    pub serialized_point_and_flag: String,
+    /// `hash_to_secp256k1(serialized_point_and_flag)`
+    pub mapped_point: String,
+    /// `g * r + member * c`
+    pub computed_point: String,
+    /// `mapped_point * r + key_image * c`
+    pub combined_point: String,
+    /// What is hashed after the serialized ring: the decimal message digest and both points
+    pub hash_input: String,
+    /// The resulting challenge of the next member, in hex
+    pub c: String,
+}
+
+/// Computes the challenge of the member following `params.previous_index` in `ring`.
+/// `ring_hasher` has already absorbed the serialized ring, so that each challenge only hashes
+/// its own points instead of the whole ring again.
+pub fn compute_c(
+    ring: &[AffinePoint],
+    ring_hasher: &Sha256,
+    message_digest: &str,
+    params: &Params,
+) -> Result<Scalar, String> {
+    challenge(ring, ring_hasher, message_digest, params, None)
+}
+
+/// Computes the challenge as [`compute_c`] does, pushing its intermediate values to `traces`
+/// when given.
+fn challenge(
+    ring: &[AffinePoint],
+    ring_hasher: &Sha256,
+    message_digest: &str,
+    params: &Params,
+    traces: Option<&mut Vec<ChallengeTrace>>,
+) -> Result<Scalar, String> {
+    let g = AffinePoint::GENERATOR;
+    let point =
+        ((g * params.previous_r) + (ring[params.previous_index] * params.previous_c)).to_affine();
+
+    let serialized_point = serialize_point(ring[params.previous_index])
+        .map_err(|e| format!("Failed to serialize point: {}", e))?;
+
+    let serialized_point_and_flag = format!(
+        "{}{}",
+        serialized_point,
+        params.linkability_flag.unwrap_or("")
+    );
+
+    let mapped = hash_to_secp256k1(&serialized_point_and_flag)
+        .map_err(|_| "Failed to map to secp256k1 point".to_string())?;
+    let decimal_digest = hex_to_decimal(message_digest)
+        .map_err(|_| "Failed to convert message digest".to_string())?;
+    let serialized_computed_point =
+        serialize_point(point).map_err(|e| format!("Failed to serialize computed point: {}", e))?;
+    let combined_point = (mapped * params.previous_r) + (params.key_image * params.previous_c);
+    let serialized_combined_point = serialize_point(combined_point.to_affine())
+        .map_err(|e| format!("Failed to serialize combined point: {}", e))?;
+    let hash_content = format!(
+        "{}{}{}",
+        decimal_digest, serialized_computed_point, serialized_combined_point
+    );
+    let mut hasher = ring_hasher.clone();
+    hasher.update(hash_content.as_bytes());
+    let hash = hex::encode(hasher.finalize());
+    let c = scalar_from_hex(&hash).map_err(|_| "Failed to convert hash to scalar".to_string())?;
+
+    if let Some(traces) = traces {
+        traces.push(ChallengeTrace {
+            index: params.previous_index,
+            serialized_point,
+            serialized_point_and_flag,
+            mapped_point: serialize_point(mapped)?,
+            computed_point: serialized_computed_point,
+            combined_point: serialized_combined_point,
+            hash_input: hash_content,
+            c: hash,
+        });
+    }
+    Ok(c)
+}
+
+/// Walks the challenge chain over consecutive ring `members`, from `c_start`, the challenge of
+/// the first member, and returns the challenge of the member following the last one.
+pub fn compute_chain(
+    members: &[AffinePoint],
+    ring_hasher: &Sha256,
+    message_digest: &str,
+    c_start: Scalar,
+    responses: &[Scalar],
+    key_image: AffinePoint,
+    linkability_flag: Option<&str>,
+) -> Result<Scalar, String> {
+    let start = Params {
+        index: 0,
+        previous_r: Scalar::ZERO,
+        previous_c: c_start,
+        previous_index: 0,
+        key_image,
+        linkability_flag,
+    };
+    walk_chain(members, ring_hasher, message_digest, responses, start, None)
+}
+
+/// Walks the challenge chain as [`compute_chain`] does, also returning the intermediate values
+/// of each challenge. An error is prefixed with the index of the member it occurred at.
+pub fn compute_chain_traced(
+    members: &[AffinePoint],
+    ring_hasher: &Sha256,
+    message_digest: &str,
+    c_start: Scalar,
+    responses: &[Scalar],
+    key_image: AffinePoint,
+    linkability_flag: Option<&str>,
+) -> Result<(Scalar, Vec<ChallengeTrace>), String> {
+    let start = Params {
+        index: 0,
+        previous_r: Scalar::ZERO,
+        previous_c: c_start,
+        previous_index: 0,
+        key_image,
+        linkability_flag,
+    };
+    let mut traces = Vec::with_capacity(members.len());
+    let c = walk_chain(
+        members,
+        ring_hasher,
+        message_digest,
+        responses,
+        start,
+        Some(&mut traces),
+    )
+    .map_err(|e| format!("Ring index {}: {}", traces.len(), e))?;
+    Ok((c, traces))
+}
+
+/// Computes the challenge of each member in turn, `params` holding the challenge to start from.
+fn walk_chain(
+    members: &[AffinePoint],
+    ring_hasher: &Sha256,
+    message_digest: &str,
+    responses: &[Scalar],
+    mut params: Params,
+    mut traces: Option<&mut Vec<ChallengeTrace>>,
+) -> Result<Scalar, String> {
+    if members.len() != responses.len() {
+        return Err("Expected one response per ring member".to_string());
+    }
+    for (i, response) in responses.iter().enumerate() {
+        params.index = i + 1;
+        params.previous_r = *response;
+        params.previous_index = i;
+        params.previous_c = challenge(
+            members,
+            ring_hasher,
+            message_digest,
+            &params,
+            traces.as_deref_mut(),
+        )?;
+    }
+    Ok(params.previous_c)
+}
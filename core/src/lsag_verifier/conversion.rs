@@ -0,0 +1,105 @@
+use crate::utils::PointEncoding;
+use alloy_dyn_abi::TypedData;
+use base64::engine::general_purpose;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::Error;
+
+/// How the `message` field of a [`StringifiedLsag`] encodes the signed bytes.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageEncoding {
+    /// The message is the UTF-8 string itself
+    #[default]
+    Utf8,
+    /// The message is hex encoded binary data, optionally `0x`-prefixed
+    Hex,
+    /// The message is base64 encoded binary data
+    Base64,
+}
+
+impl MessageEncoding {
+    /// Decodes `message` into the bytes that were signed
+    pub fn decode(&self, message: &str) -> Result<Vec<u8>, String> {
+        match self {
+            MessageEncoding::Utf8 => Ok(message.as_bytes().to_vec()),
+            MessageEncoding::Hex => hex::decode(message.strip_prefix("0x").unwrap_or(message))
+                .map_err(|_| "Invalid hex message".to_string()),
+            MessageEncoding::Base64 => general_purpose::STANDARD
+                .decode(message)
+                .map_err(|_| "Invalid base64 message".to_string()),
+        }
+    }
+}
+
+/// How the signed message digest is derived.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageFormat {
+    /// The digest is the sha256 of `message`, or is given as `messageDigest`
+    #[default]
+    Raw,
+    /// The digest is the EIP-191 personal message hash of `message`
+    Eip191,
+    /// The digest is the EIP-712 signing hash of `typedData`
+    Eip712,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+pub struct StringifiedLsag {
+    /// The signed message. Mutually exclusive with `messageDigest`
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub messageEncoding: MessageEncoding,
+    /// The hex sha256 digest of the signed message, for pre-hashed signatures
+    #[serde(default)]
+    pub messageDigest: Option<String>,
+    #[serde(default)]
+    pub messageFormat: MessageFormat,
+    /// The EIP-712 typed data payload, for `eip712` messages
+    #[serde(default)]
+    pub typedData: Option<TypedData>,
+    pub ring: Vec<PointEncoding>,
+    pub c: String,
+    pub responses: Vec<String>,
+    pub keyImage: PointEncoding,
+    pub linkabilityFlag: String,
+}
+
+pub fn convert_string_to_json(json_str: &str) -> Result<StringifiedLsag, Error> {
+    serde_json::from_str(json_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_encodings_decode_to_the_same_bytes() {
+        let bytes = b"Hello World".to_vec();
+        assert_eq!(MessageEncoding::Utf8.decode("Hello World").unwrap(), bytes);
+        assert_eq!(
+            MessageEncoding::Hex
+                .decode("0x48656c6c6f20576f726c64")
+                .unwrap(),
+            bytes
+        );
+        assert_eq!(
+            MessageEncoding::Base64.decode("SGVsbG8gV29ybGQ=").unwrap(),
+            bytes
+        );
+        assert!(MessageEncoding::Hex.decode("0xzz").is_err());
+    }
+
+    #[test]
+    fn message_encoding_defaults_to_utf8() {
+        let json = r#"{"message":"Hello World","ring":[],"c":"","responses":[],"keyImage":"","linkabilityFlag":""}"#;
+        let lsag = convert_string_to_json(json).unwrap();
+        assert_eq!(lsag.messageEncoding, MessageEncoding::Utf8);
+        let json = json.replace(r#""ring""#, r#""messageEncoding":"base64","ring""#);
+        let lsag = convert_string_to_json(&json).unwrap();
+        assert_eq!(lsag.messageEncoding, MessageEncoding::Base64);
+    }
+}
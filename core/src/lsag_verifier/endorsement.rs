@@ -0,0 +1,50 @@
+use crate::lsag_verifier::lsag_verifier::{decode_b64_lsag, resolve_message, verify_b64_lsag};
+use crate::lsag_verifier::minimal::MinimalMessage;
+use sha2::{Digest, Sha256};
+
+/// Verifies a base64-encoded LSAG endorsing the journal of another receipt.
+///
+/// The signed message must be the 32-byte sha256 digest of `endorsed_journal`, given as a
+/// binary message (e.g. hex encoded with `"messageEncoding": "hex"`). The receipt of the
+/// endorsed journal is checked by the caller.
+///
+/// # Returns
+/// * `Some([u8; 32])` - The minimal LSAG digest, as committed by `verify_b64_lsag`, if the signature is valid.
+/// * `None` - If the signature is invalid or signs another message.
+pub fn verify_b64_endorsement(b64_signature: String, endorsed_journal: &[u8]) -> Option<[u8; 32]> {
+    let json = decode_b64_lsag(&b64_signature)?;
+    let journal_digest = Sha256::digest(endorsed_journal);
+    match resolve_message(&json)? {
+        (_, MinimalMessage::Full(message)) if message == journal_digest.as_slice() => {
+            verify_b64_lsag(b64_signature)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsag_verifier::signer::sample_b64_lsag_with_bytes;
+
+    // publisher demo signature, whose message is "Hello World"
+    const SIGNATURE: &str = "eyJtZXNzYWdlIjoiSGVsbG8gV29ybGQiLCJyaW5nIjpbIjAyMjE4NjljYTNhZTMzYmUzYTczMjdlOWEwMjcyMjAzYWZhNzJjNTJhNTQ2MGNlYjlmNGE1MDkzMDUzMWJkOTI2YSIsIjAzNDI4ZTAyMGYxODRiNzBjYTkzMWE5MTA4NWFjMWMyMzM4MjdhNDFkODUxNmE0YjY0NTVlMjIxZTYzN2M0ZGUwZiIsIjAzNDM4ZmJmMzc3NmNjMjRlMjUzNTgyMjU0NGYxNGQ3YjA1N2Q4OTU3YzcyNjc2MDE4MDA1MmNkYTdiOGJhNmM4MyIsIjAzNTBjMWJkNjRjMzA4N2Y2NWY0ODE3MTdlZTRhNWJkZmJiYTRmMDYwMzE0OTkzZjFlMTVjMGRiMjk3NDhiOGRjMiJdLCJjIjoiM2M3ZDBhYzE4YjBlYWU4N2M1OTFjMGM5ZWRkOWE3ZDU3YjI5ZWUxZDhiNzZlYTFjOGM1NjAxMDQ3MGMwZDViMiIsInJlc3BvbnNlcyI6WyIxOWUzNGNjOTc5Y2E1YWMzYTk2MThkMGNlZThmYjdmNzRlMmY4MzA5MmY2ZDZmOTUyZTA3OWYxMzY1MmNlNjM2IiwiYjRkZGE5ZTc4YzA4OTliYjFjYmNkYTVjMjhiYWRiZjYwYWIzMDc1N2MyZjVhMWIxNWQwZDliNmQ1MzdhMTMwMSIsIjJhY2Q4ZWIzMzZhZjU5YzIwMTVhNDljMGJlMWZhZmE3Yzk0ODRmYWQ4YmY3MmFmYjZjYmIwYzgzMDhhOGUxODUiLCI1Y2IzNWY3OWVmYzBmODEwYTI0NTMxYjU0YWM0NThiNjZkMTZlNzNhMTdjOWEyY2IxYTkyN2QzYzI1YTNkMDY4Il0sImN1cnZlIjoie1wiY3VydmVcIjpcIlNFQ1AyNTZLMVwifSIsImtleUltYWdlIjoiMDJlN2ZmMzQ5MGVlN2RiMzM3NTBmZTlhNzA5MWQ4MmRjYTk1MmU2ZDIyYTdmZDRkZjk3ZDBjYmY4ZjdjYjQ2YWQyIiwibGlua2FiaWxpdHlGbGFnIjoibGlua2FiaWxpdHkiLCJjb25maWciOnsiaGFzaCI6InNoYTI1NiJ9fQ==";
+
+    #[test]
+    fn rejects_a_message_other_than_the_journal_digest() {
+        assert!(verify_b64_lsag(SIGNATURE.to_string()).is_some());
+        assert!(verify_b64_endorsement(SIGNATURE.to_string(), b"Hello World").is_none());
+    }
+
+    #[test]
+    fn accepts_a_signature_of_the_journal_digest() {
+        let journal = b"journal of the endorsed receipt";
+        let digest = Sha256::digest(journal);
+        let signature = sample_b64_lsag_with_bytes(&digest, "endorsement", 3, 1).unwrap();
+
+        let endorsement = verify_b64_endorsement(signature.clone(), journal);
+        assert!(endorsement.is_some());
+        assert_eq!(endorsement, verify_b64_lsag(signature.clone()));
+        assert!(verify_b64_endorsement(signature, b"another journal").is_none());
+    }
+}
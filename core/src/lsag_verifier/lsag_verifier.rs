@@ -0,0 +1,474 @@
+use crate::lsag_verifier::compute::{compute_chain, compute_chain_traced, ChallengeTrace};
+use crate::lsag_verifier::conversion::{convert_string_to_json, MessageFormat, StringifiedLsag};
+use crate::lsag_verifier::minimal::{digest_minimal_lsag, MinimalLsag, MinimalMessage};
+use crate::lsag_verifier::status::LsagStatus;
+use crate::utils::decode_ring;
+use crate::utils::digest_from_hex;
+use crate::utils::scalar_from_hex;
+use crate::utils::serialize_ring;
+use alloy_primitives::{eip191_hash_message, keccak256};
+use base64::engine::general_purpose;
+use base64::Engine;
+use core::str;
+use k256::{AffinePoint, Scalar};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Verifies a base64-encoded LSAG (Linkable Spontaneous Anonymous Group) signature.
+///
+/// This function decodes the base64 string, parses the resulting JSON, and verifies the ring signature.
+/// The JSON carries either the signed `message` or, for pre-hashed signatures, its `messageDigest`.
+/// With `messageFormat` set to `eip191` or `eip712`, the signed digest is the standard Ethereum
+/// digest of `message` or `typedData`.
+///
+/// # Arguments
+/// * `b64_signature` - A base64-encoded LSAG signature.
+///
+/// # Returns
+/// * `Some([u8; 32])` - Returns a 32-byte hash if the signature is valid.
+/// * `None` - Returns `None` if the signature verification fails.
+pub fn verify_b64_lsag(b64_signature: String) -> Option<[u8; 32]> {
+    try_verify_b64_lsag(&b64_signature).ok()
+}
+
+/// Verifies a base64-encoded LSAG signature as [`verify_b64_lsag`] does, reporting why it is
+/// rejected instead of panicking on invalid input.
+///
+/// # Returns
+/// * `Ok([u8; 32])` - The 32-byte hash committed for a valid signature.
+/// * `Err(LsagStatus)` - The class of the failure, never `LsagStatus::Valid`.
+pub fn try_verify_b64_lsag(b64_signature: &str) -> Result<[u8; 32], LsagStatus> {
+    try_verify_b64_lsag_with_phases(b64_signature, |_| {})
+}
+
+/// The phases of the verification of a base64-encoded LSAG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Decoding the base64 JSON
+    Parsing,
+    /// Decoding the ring, the key image and the scalars
+    RingDeserialization,
+    /// Resolving the signed message digest, e.g. hashing EIP-712 typed data
+    MessageDigest,
+    /// Walking the challenge chain
+    ChallengeChain,
+    /// Computing the digest of the ABI encoded minimal LSAG
+    AbiDigest,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Parsing => "parsing",
+            Phase::RingDeserialization => "ring_deserialization",
+            Phase::MessageDigest => "message_digest",
+            Phase::ChallengeChain => "challenge_chain",
+            Phase::AbiDigest => "abi_digest",
+        }
+    }
+}
+
+/// Verifies a base64-encoded LSAG signature as [`try_verify_b64_lsag`] does, calling
+/// `on_phase_end` at the end of each phase, e.g. to measure its cost in the guest.
+/// Phases that are not reached because of an invalid input are not reported.
+pub fn try_verify_b64_lsag_with_phases(
+    b64_signature: &str,
+    mut on_phase_end: impl FnMut(Phase),
+) -> Result<[u8; 32], LsagStatus> {
+    let json = decode_b64_lsag(b64_signature).ok_or(LsagStatus::MalformedInput)?;
+    on_phase_end(Phase::Parsing);
+
+    let ring_points = decode_ring(&json.ring).map_err(|_| LsagStatus::InvalidPoint)?;
+    let key_image = json
+        .keyImage
+        .decode()
+        .map_err(|_| LsagStatus::InvalidPoint)?;
+
+    let c0 = scalar_from_hex(&json.c).map_err(|_| LsagStatus::InvalidScalar)?;
+    let responses = json
+        .responses
+        .iter()
+        .map(|response| scalar_from_hex(response))
+        .collect::<Result<Vec<Scalar>, String>>()
+        .map_err(|_| LsagStatus::InvalidScalar)?;
+    if ring_points.is_empty() || ring_points.len() != responses.len() {
+        return Err(LsagStatus::RingSizeMismatch);
+    }
+    on_phase_end(Phase::RingDeserialization);
+
+    // resolved after the ring and the scalars, so that an input with several faults reports
+    // the same status as it always did
+    let (message_digest, message) = resolve_message(&json).ok_or(LsagStatus::InvalidMessage)?;
+    let linkability_flag = Some(json.linkabilityFlag.as_str());
+    on_phase_end(Phase::MessageDigest);
+
+    let is_valid = verify_lsag_digest(
+        &ring_points,
+        &message_digest,
+        c0,
+        &responses,
+        key_image,
+        linkability_flag,
+    );
+    on_phase_end(Phase::ChallengeChain);
+
+    if is_valid {
+        let hash = digest_minimal_lsag(&MinimalLsag {
+            message,
+            key_image,
+            linkability_flag,
+            ring: ring_points,
+        });
+        on_phase_end(Phase::AbiDigest);
+        Ok(hash)
+    } else {
+        Err(LsagStatus::InvalidSignature)
+    }
+}
+
+/// Decodes the JSON of a base64-encoded LSAG signature.
+pub fn decode_b64_lsag(b64_signature: &str) -> Option<StringifiedLsag> {
+    let decoded_bytes = general_purpose::STANDARD
+        .decode(b64_signature.as_bytes())
+        .ok()?;
+    let decoded_string = str::from_utf8(&decoded_bytes).ok()?;
+    convert_string_to_json(decoded_string).ok()
+}
+
+/// Resolves the digest the signer committed to, and the message data bound in the journal.
+pub fn resolve_message(json: &StringifiedLsag) -> Option<([u8; 32], MinimalMessage)> {
+    match (
+        json.messageFormat,
+        &json.message,
+        &json.messageDigest,
+        &json.typedData,
+    ) {
+        (MessageFormat::Raw, Some(message), None, None) => {
+            let message = json.messageEncoding.decode(message).ok()?;
+            let digest = Sha256::digest(&message).into();
+            Some((digest, MinimalMessage::Full(message)))
+        }
+        (MessageFormat::Raw, None, Some(message_digest), None) => {
+            let digest = digest_from_hex(message_digest).ok()?;
+            Some((digest, MinimalMessage::Digest(digest)))
+        }
+        (MessageFormat::Eip191, Some(message), None, None) => {
+            let message = json.messageEncoding.decode(message).ok()?;
+            let digest = eip191_hash_message(&message).0;
+            Some((digest, MinimalMessage::Eip191 { message, digest }))
+        }
+        (MessageFormat::Eip712, None, None, Some(typed_data)) => {
+            let domain_separator = typed_data.domain().separator().0;
+            let struct_hash = typed_data.hash_struct().ok()?.0;
+            let digest =
+                keccak256([&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat()).0;
+            Some((
+                digest,
+                MinimalMessage::Eip712 {
+                    domain_separator,
+                    struct_hash,
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Verifies a ring signature (LSAG).
+///
+/// # Arguments
+/// * `ring` - A list of public keys (ring) used in the signature.
+/// * `message` - The bytes that were signed.
+/// * `c0` - The initial scalar value (challenge).
+/// * `responses` - The response scalars for each ring member.
+/// * `key_image` - The key image used in the signature.
+/// * `linkability_flag` - Optional flag for linkability.
+///
+/// # Returns
+/// * `true` if the signature is valid, `false` otherwise.
+pub fn verify_lsag(
+    ring: &[AffinePoint],
+    message: &[u8],
+    c0: Scalar,
+    responses: &[Scalar],
+    key_image: AffinePoint,
+    linkability_flag: Option<&str>,
+) -> bool {
+    let message_digest: [u8; 32] = Sha256::digest(message).into();
+    verify_lsag_digest(
+        ring,
+        &message_digest,
+        c0,
+        responses,
+        key_image,
+        linkability_flag,
+    )
+}
+
+/// Verifies a ring signature (LSAG) given the digest of the signed message.
+///
+/// # Arguments
+/// * `ring` - A list of public keys (ring) used in the signature.
+/// * `message_digest` - The 32-byte digest of the signed message, used as-is in the challenges.
+/// * `c0` - The initial scalar value (challenge).
+/// * `responses` - The response scalars for each ring member.
+/// * `key_image` - The key image used in the signature.
+/// * `linkability_flag` - Optional flag for linkability.
+///
+/// # Returns
+/// * `true` if the signature is valid, `false` otherwise.
+pub fn verify_lsag_digest(
+    ring: &[AffinePoint],
+    message_digest: &[u8; 32],
+    c0: Scalar,
+    responses: &[Scalar],
+    key_image: AffinePoint,
+    linkability_flag: Option<&str>,
+) -> bool {
+    if ring.is_empty() || ring.len() != responses.len() {
+        return false;
+    }
+
+    let message_digest = hex::encode(message_digest);
+    let serialized_ring = match serialize_ring(ring) {
+        Ok(result) => result,
+        Err(_) => return false, // Return false if there's an error in serializing the ring
+    };
+    let ring_hasher = Sha256::new_with_prefix(serialized_ring.as_bytes());
+
+    match compute_chain(
+        ring,
+        &ring_hasher,
+        &message_digest,
+        c0,
+        responses,
+        key_image,
+        linkability_flag,
+    ) {
+        Ok(last_computed_c) => c0 == last_computed_c,
+        Err(_) => false, // Return false if compute_c returns an error
+    }
+}
+
+/// The intermediate values of the verification of a ring signature, to diagnose signer mismatches.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LsagTrace {
+    /// The serialized ring, hashed first in every challenge
+    pub serialized_ring: String,
+    /// The message digest, in hex
+    pub message_digest: String,
+    /// The challenge `c0`, in hex
+    pub c0: String,
+    /// One trace per ring member, in the order of the challenge chain
+    pub challenges: Vec<ChallengeTrace>,
+    /// Whether the chain closes at `c0`
+    pub valid: bool,
+}
+
+/// Verifies a base64-encoded LSAG signature as [`verify_b64_lsag`] does, returning every
+/// intermediate value of the challenge chain.
+pub fn trace_b64_lsag(b64_signature: &str) -> Result<LsagTrace, String> {
+    let json = decode_b64_lsag(b64_signature).ok_or("Invalid signature encoding")?;
+    let ring_points = decode_ring(&json.ring)?;
+    let key_image = json.keyImage.decode()?;
+    let responses = json
+        .responses
+        .iter()
+        .map(|response| scalar_from_hex(response))
+        .collect::<Result<Vec<Scalar>, String>>()?;
+    let (message_digest, _) = resolve_message(&json).ok_or("Invalid message")?;
+
+    trace_lsag_digest(
+        &ring_points,
+        &message_digest,
+        scalar_from_hex(&json.c)?,
+        &responses,
+        key_image,
+        Some(json.linkabilityFlag.as_str()),
+    )
+}
+
+/// Verifies a ring signature (LSAG) as [`verify_lsag_digest`] does, returning every
+/// intermediate value of the challenge chain.
+/// The trace stops at the first challenge that cannot be computed, returning its error.
+pub fn trace_lsag_digest(
+    ring: &[AffinePoint],
+    message_digest: &[u8; 32],
+    c0: Scalar,
+    responses: &[Scalar],
+    key_image: AffinePoint,
+    linkability_flag: Option<&str>,
+) -> Result<LsagTrace, String> {
+    if ring.is_empty() || ring.len() != responses.len() {
+        return Err("Expected one response per ring member".to_string());
+    }
+
+    let message_digest = hex::encode(message_digest);
+    let serialized_ring = serialize_ring(ring)?;
+    let ring_hasher = Sha256::new_with_prefix(serialized_ring.as_bytes());
+    let (last_computed_c, challenges) = compute_chain_traced(
+        ring,
+        &ring_hasher,
+        &message_digest,
+        c0,
+        responses,
+        key_image,
+        linkability_flag,
+    )?;
+
+    Ok(LsagTrace {
+        serialized_ring,
+        message_digest,
+        c0: hex::encode(c0.to_bytes()),
+        challenges,
+        valid: c0 == last_computed_c,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsag_verifier::conversion::convert_string_to_json;
+
+    fn resolve(fields: &str) -> Option<([u8; 32], MinimalMessage)> {
+        let json = format!(
+            r#"{{{},"ring":[],"c":"","responses":[],"keyImage":"","linkabilityFlag":""}}"#,
+            fields
+        );
+        resolve_message(&convert_string_to_json(&json).unwrap())
+    }
+
+    #[test]
+    fn eip191_digest_is_the_personal_message_hash() {
+        let (digest, message) =
+            resolve(r#""message":"Hello World","messageFormat":"eip191""#).unwrap();
+        assert_eq!(
+            hex::encode(digest),
+            "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+        );
+        assert!(matches!(message, MinimalMessage::Eip191 { .. }));
+    }
+
+    #[test]
+    fn eip712_digest_is_the_typed_data_signing_hash() {
+        // "Mail" example from the EIP-712 specification
+        let typed_data = r#"{
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Person": [
+                    {"name": "name", "type": "string"},
+                    {"name": "wallet", "type": "address"}
+                ],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"}
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!"
+            }
+        }"#;
+        let (digest, message) = resolve(&format!(
+            r#""messageFormat":"eip712","typedData":{}"#,
+            typed_data
+        ))
+        .unwrap();
+        assert_eq!(
+            hex::encode(digest),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+        match message {
+            MinimalMessage::Eip712 {
+                domain_separator,
+                struct_hash,
+            } => {
+                assert_eq!(
+                    hex::encode(domain_separator),
+                    "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+                );
+                assert_eq!(
+                    hex::encode(struct_hash),
+                    "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+                );
+            }
+            _ => panic!("expected an EIP-712 message"),
+        }
+    }
+
+    #[test]
+    fn message_fields_must_match_the_format() {
+        assert!(resolve(r#""message":"a","messageDigest":"00""#).is_none());
+        assert!(resolve(r#""messageFormat":"eip191""#).is_none());
+        assert!(resolve(r#""message":"a","messageFormat":"eip712""#).is_none());
+    }
+
+    #[test]
+    fn reports_the_class_of_failure() {
+        // publisher demo signature
+        let demo = "eyJtZXNzYWdlIjoiSGVsbG8gV29ybGQiLCJyaW5nIjpbIjAyMjE4NjljYTNhZTMzYmUzYTczMjdlOWEwMjcyMjAzYWZhNzJjNTJhNTQ2MGNlYjlmNGE1MDkzMDUzMWJkOTI2YSIsIjAzNDI4ZTAyMGYxODRiNzBjYTkzMWE5MTA4NWFjMWMyMzM4MjdhNDFkODUxNmE0YjY0NTVlMjIxZTYzN2M0ZGUwZiIsIjAzNDM4ZmJmMzc3NmNjMjRlMjUzNTgyMjU0NGYxNGQ3YjA1N2Q4OTU3YzcyNjc2MDE4MDA1MmNkYTdiOGJhNmM4MyIsIjAzNTBjMWJkNjRjMzA4N2Y2NWY0ODE3MTdlZTRhNWJkZmJiYTRmMDYwMzE0OTkzZjFlMTVjMGRiMjk3NDhiOGRjMiJdLCJjIjoiM2M3ZDBhYzE4YjBlYWU4N2M1OTFjMGM5ZWRkOWE3ZDU3YjI5ZWUxZDhiNzZlYTFjOGM1NjAxMDQ3MGMwZDViMiIsInJlc3BvbnNlcyI6WyIxOWUzNGNjOTc5Y2E1YWMzYTk2MThkMGNlZThmYjdmNzRlMmY4MzA5MmY2ZDZmOTUyZTA3OWYxMzY1MmNlNjM2IiwiYjRkZGE5ZTc4YzA4OTliYjFjYmNkYTVjMjhiYWRiZjYwYWIzMDc1N2MyZjVhMWIxNWQwZDliNmQ1MzdhMTMwMSIsIjJhY2Q4ZWIzMzZhZjU5YzIwMTVhNDljMGJlMWZhZmE3Yzk0ODRmYWQ4YmY3MmFmYjZjYmIwYzgzMDhhOGUxODUiLCI1Y2IzNWY3OWVmYzBmODEwYTI0NTMxYjU0YWM0NThiNjZkMTZlNzNhMTdjOWEyY2IxYTkyN2QzYzI1YTNkMDY4Il0sImN1cnZlIjoie1wiY3VydmVcIjpcIlNFQ1AyNTZLMVwifSIsImtleUltYWdlIjoiMDJlN2ZmMzQ5MGVlN2RiMzM3NTBmZTlhNzA5MWQ4MmRjYTk1MmU2ZDIyYTdmZDRkZjk3ZDBjYmY4ZjdjYjQ2YWQyIiwibGlua2FiaWxpdHlGbGFnIjoibGlua2FiaWxpdHkiLCJjb25maWciOnsiaGFzaCI6InNoYTI1NiJ9fQ==";
+        let tampered = |field: &str, value: serde_json::Value| {
+            let decoded = general_purpose::STANDARD.decode(demo).unwrap();
+            let mut json: serde_json::Value = serde_json::from_slice(&decoded).unwrap();
+            json[field] = value;
+            general_purpose::STANDARD.encode(json.to_string())
+        };
+
+        assert!(try_verify_b64_lsag(demo).is_ok());
+        assert_eq!(
+            try_verify_b64_lsag("not base64"),
+            Err(LsagStatus::MalformedInput)
+        );
+        assert_eq!(
+            try_verify_b64_lsag(&tampered("keyImage", "02".into())),
+            Err(LsagStatus::InvalidPoint)
+        );
+        assert_eq!(
+            try_verify_b64_lsag(&tampered("c", "ff".repeat(32).into())),
+            Err(LsagStatus::InvalidScalar)
+        );
+        assert_eq!(
+            try_verify_b64_lsag(&tampered("responses", serde_json::json!([]))),
+            Err(LsagStatus::RingSizeMismatch)
+        );
+        assert_eq!(
+            try_verify_b64_lsag(&tampered("messageDigest", "00".into())),
+            Err(LsagStatus::InvalidMessage)
+        );
+        assert_eq!(
+            try_verify_b64_lsag(&tampered("message", "Hello World!".into())),
+            Err(LsagStatus::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn trace_follows_the_challenge_chain() {
+        // publisher demo signature
+        let demo = "eyJtZXNzYWdlIjoiSGVsbG8gV29ybGQiLCJyaW5nIjpbIjAyMjE4NjljYTNhZTMzYmUzYTczMjdlOWEwMjcyMjAzYWZhNzJjNTJhNTQ2MGNlYjlmNGE1MDkzMDUzMWJkOTI2YSIsIjAzNDI4ZTAyMGYxODRiNzBjYTkzMWE5MTA4NWFjMWMyMzM4MjdhNDFkODUxNmE0YjY0NTVlMjIxZTYzN2M0ZGUwZiIsIjAzNDM4ZmJmMzc3NmNjMjRlMjUzNTgyMjU0NGYxNGQ3YjA1N2Q4OTU3YzcyNjc2MDE4MDA1MmNkYTdiOGJhNmM4MyIsIjAzNTBjMWJkNjRjMzA4N2Y2NWY0ODE3MTdlZTRhNWJkZmJiYTRmMDYwMzE0OTkzZjFlMTVjMGRiMjk3NDhiOGRjMiJdLCJjIjoiM2M3ZDBhYzE4YjBlYWU4N2M1OTFjMGM5ZWRkOWE3ZDU3YjI5ZWUxZDhiNzZlYTFjOGM1NjAxMDQ3MGMwZDViMiIsInJlc3BvbnNlcyI6WyIxOWUzNGNjOTc5Y2E1YWMzYTk2MThkMGNlZThmYjdmNzRlMmY4MzA5MmY2ZDZmOTUyZTA3OWYxMzY1MmNlNjM2IiwiYjRkZGE5ZTc4YzA4OTliYjFjYmNkYTVjMjhiYWRiZjYwYWIzMDc1N2MyZjVhMWIxNWQwZDliNmQ1MzdhMTMwMSIsIjJhY2Q4ZWIzMzZhZjU5YzIwMTVhNDljMGJlMWZhZmE3Yzk0ODRmYWQ4YmY3MmFmYjZjYmIwYzgzMDhhOGUxODUiLCI1Y2IzNWY3OWVmYzBmODEwYTI0NTMxYjU0YWM0NThiNjZkMTZlNzNhMTdjOWEyY2IxYTkyN2QzYzI1YTNkMDY4Il0sImN1cnZlIjoie1wiY3VydmVcIjpcIlNFQ1AyNTZLMVwifSIsImtleUltYWdlIjoiMDJlN2ZmMzQ5MGVlN2RiMzM3NTBmZTlhNzA5MWQ4MmRjYTk1MmU2ZDIyYTdmZDRkZjk3ZDBjYmY4ZjdjYjQ2YWQyIiwibGlua2FiaWxpdHlGbGFnIjoibGlua2FiaWxpdHkiLCJjb25maWciOnsiaGFzaCI6InNoYTI1NiJ9fQ==";
+        let trace = trace_b64_lsag(demo).unwrap();
+        assert!(trace.valid);
+        assert_eq!(trace.challenges.len(), 4);
+        assert_eq!(trace.challenges.last().unwrap().c, trace.c0);
+        assert_eq!(
+            trace.challenges[1].serialized_point_and_flag,
+            "03428e020f184b70ca931a91085ac1c233827a41d8516a4b6455e221e637c4de0flinkability"
+        );
+        assert!(trace.challenges[0]
+            .hash_input
+            .ends_with(&trace.challenges[0].combined_point));
+    }
+}
@@ -0,0 +1,141 @@
+use ethabi::ethereum_types::U256;
+use ethabi::{encode, Token};
+use k256::{elliptic_curve::sec1::ToEncodedPoint, AffinePoint};
+use sha2::{Digest, Sha256};
+
+struct PointCoordinates {
+    x: U256,
+    y: U256,
+}
+
+/// The message field of a minimal LSAG
+#[derive(Debug)]
+pub enum MinimalMessage {
+    /// The signed message, ABI encoded as `string` / `bytes`
+    Full(Vec<u8>),
+    /// The sha256 digest of the signed message, ABI encoded as `bytes32`
+    Digest([u8; 32]),
+    /// An EIP-191 personal message, ABI encoded as its `bytes32` digest followed by the `bytes` message
+    Eip191 { message: Vec<u8>, digest: [u8; 32] },
+    /// An EIP-712 typed data payload, ABI encoded as its `bytes32` domain separator and struct hash
+    Eip712 {
+        domain_separator: [u8; 32],
+        struct_hash: [u8; 32],
+    },
+}
+
+impl MinimalMessage {
+    fn tokens(&self) -> Vec<Token> {
+        match self {
+            // `string` and `bytes` share the same ABI encoding, so the digest matches both
+            // `RingSignatureData` and `BytesRingSignatureData` on chain.
+            MinimalMessage::Full(message) => vec![Token::Bytes(message.clone())],
+            MinimalMessage::Digest(digest) => vec![Token::FixedBytes(digest.to_vec())],
+            MinimalMessage::Eip191 { message, digest } => vec![
+                Token::FixedBytes(digest.to_vec()),
+                Token::Bytes(message.clone()),
+            ],
+            MinimalMessage::Eip712 {
+                domain_separator,
+                struct_hash,
+            } => vec![
+                Token::FixedBytes(domain_separator.to_vec()),
+                Token::FixedBytes(struct_hash.to_vec()),
+            ],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MinimalLsag<'a> {
+    pub message: MinimalMessage,
+    pub key_image: AffinePoint,
+    pub linkability_flag: Option<&'a str>,
+    pub ring: Vec<AffinePoint>,
+}
+
+// Convert a lsag to a minimal LSAG and return the sha256 digest of the data
+pub fn to_minimal_lsag_digest(
+    ring: &[AffinePoint],
+    message: &[u8],
+    key_image: AffinePoint,
+    linkability_flag: Option<&str>,
+) -> [u8; 32] {
+    let mini_lsag = MinimalLsag {
+        message: MinimalMessage::Full(message.to_vec()),
+        linkability_flag,
+        key_image,
+        ring: ring.to_vec(),
+    };
+    digest_minimal_lsag(&mini_lsag)
+}
+
+// Convert a pre-hashed lsag to a minimal LSAG binding only the message digest
+// and return the sha256 digest of the data
+pub fn to_prehashed_minimal_lsag_digest(
+    ring: &[AffinePoint],
+    message_digest: &[u8; 32],
+    key_image: AffinePoint,
+    linkability_flag: Option<&str>,
+) -> [u8; 32] {
+    let mini_lsag = MinimalLsag {
+        message: MinimalMessage::Digest(*message_digest),
+        linkability_flag,
+        key_image,
+        ring: ring.to_vec(),
+    };
+    digest_minimal_lsag(&mini_lsag)
+}
+
+// Return the sha256 digest of the ABI encoded minimal LSAG
+pub fn digest_minimal_lsag(lsag: &MinimalLsag) -> [u8; 32] {
+    let encoded = abi_encode_minimal_lsag(lsag);
+    let mut hasher = Sha256::new();
+    hasher.update(encoded);
+    hasher.finalize().into()
+}
+
+// ABI encode the minimal lsag
+fn abi_encode_minimal_lsag(lsag: &MinimalLsag) -> Vec<u8> {
+    //set the offset
+    let mut result = vec![0u8; 32];
+    result[31] = 32u8;
+
+    // Convert key_image to point coordinates
+    let key_image_coords = affine_point_to_coordinates(&lsag.key_image);
+
+    // Convert ring points to array of point coordinates
+    let ring_points: Vec<Token> = lsag
+        .ring
+        .iter()
+        .map(|point| {
+            let coords = affine_point_to_coordinates(point);
+            Token::Tuple(vec![Token::Uint(coords.x), Token::Uint(coords.y)])
+        })
+        .collect();
+
+    let mut tokens = lsag.message.tokens();
+    tokens.extend([
+        Token::String(lsag.linkability_flag.unwrap_or_default().to_string()),
+        Token::Tuple(vec![
+            Token::Uint(key_image_coords.x),
+            Token::Uint(key_image_coords.y),
+        ]),
+        Token::Array(ring_points),
+    ]);
+
+    result.extend(encode(&tokens));
+    result
+}
+
+fn affine_point_to_coordinates(point: &AffinePoint) -> PointCoordinates {
+    let encoded = point.to_encoded_point(false);
+
+    let x_bytes = encoded.x().unwrap();
+    let y_bytes = encoded.y().unwrap();
+
+    PointCoordinates {
+        x: U256::from_big_endian(x_bytes),
+        y: U256::from_big_endian(y_bytes),
+    }
+}
@@ -0,0 +1,12 @@
+pub mod aggregation;
+pub mod chunked;
+pub mod compute;
+pub mod conversion;
+pub mod endorsement;
+#[allow(clippy::module_inception)]
+pub mod lsag_verifier;
+pub mod minimal;
+#[cfg(feature = "test-utils")]
+pub mod signer;
+pub mod status;
+pub use lsag_verifier::{trace_b64_lsag, try_verify_b64_lsag, verify_b64_lsag};
@@ -0,0 +1,256 @@
+use crate::lsag_verifier::compute::{compute_c, Params};
+use crate::utils::serialize_ring;
+use crate::utils::{hash_to_secp256k1, scalar_to_string::scalar_to_string, serialize_point};
+use base64::engine::general_purpose;
+use base64::Engine;
+use k256::elliptic_curve::ops::Reduce;
+use k256::{AffinePoint, ProjectivePoint, Scalar, U256};
+use sha2::{Digest, Sha256};
+
+/// The scalars and key image of a ring signature, as checked by `verify_lsag_digest`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LsagSignature {
+    pub c0: Scalar,
+    pub responses: Vec<Scalar>,
+    pub key_image: AffinePoint,
+}
+
+/// Signs `message_digest` on behalf of `ring[signer_index]`, whose private key is `private_key`.
+///
+/// The signer takes its randomness from the caller: `nonce` is the secret commitment of the
+/// signer, and `responses` holds one random response per ring member, the one at
+/// `signer_index` being replaced. Reusing a nonce for two messages reveals the private key.
+///
+/// # Returns
+/// * `Ok(LsagSignature)` - A signature that `verify_lsag_digest` accepts.
+/// * `Err(String)` - If the private key does not match the signer, or the sizes mismatch.
+pub fn sign_lsag_digest(
+    ring: &[AffinePoint],
+    message_digest: &[u8; 32],
+    signer_index: usize,
+    private_key: Scalar,
+    nonce: Scalar,
+    responses: &[Scalar],
+    linkability_flag: Option<&str>,
+) -> Result<LsagSignature, String> {
+    if signer_index >= ring.len() || ring.len() != responses.len() {
+        return Err("Expected one response per ring member".to_string());
+    }
+    if (ProjectivePoint::GENERATOR * private_key).to_affine() != ring[signer_index] {
+        return Err("The private key does not match the signer".to_string());
+    }
+
+    let key_image = key_image(&ring[signer_index], private_key, linkability_flag)?;
+    let message_digest = hex::encode(message_digest);
+    let ring_hasher = Sha256::new_with_prefix(serialize_ring(ring)?.as_bytes());
+    let next = |index: usize, r: Scalar, c: Scalar| {
+        let params = Params {
+            index: (index + 1) % ring.len(),
+            previous_r: r,
+            previous_c: c,
+            previous_index: index,
+            key_image,
+            linkability_flag,
+        };
+        compute_c(ring, &ring_hasher, &message_digest, &params)
+    };
+
+    // with a zero challenge, the points hashed for the signer are `g * nonce` and
+    // `mapped_point * nonce`, the commitments of the signer
+    let mut responses = responses.to_vec();
+    let mut challenges = vec![Scalar::ZERO; ring.len()];
+    let mut c = next(signer_index, nonce, Scalar::ZERO)?;
+    for offset in 1..ring.len() {
+        let index = (signer_index + offset) % ring.len();
+        challenges[index] = c;
+        c = next(index, responses[index], c)?;
+    }
+    challenges[signer_index] = c;
+    responses[signer_index] = nonce - c * private_key;
+
+    Ok(LsagSignature {
+        c0: challenges[0],
+        responses,
+        key_image,
+    })
+}
+
+/// Computes the key image of `public_key`: its serialization concatenated with the
+/// linkability flag, mapped to the curve and multiplied by the private key.
+pub fn key_image(
+    public_key: &AffinePoint,
+    private_key: Scalar,
+    linkability_flag: Option<&str>,
+) -> Result<AffinePoint, String> {
+    let serialized_point_and_flag = format!(
+        "{}{}",
+        serialize_point(*public_key)?,
+        linkability_flag.unwrap_or("")
+    );
+    let mapped = hash_to_secp256k1(&serialized_point_and_flag)?;
+    Ok((mapped * private_key).to_affine())
+}
+
+/// Encodes a signature of the utf-8 `message` as the base64 JSON read by `verify_b64_lsag`.
+pub fn encode_b64_lsag(
+    message: &str,
+    ring: &[AffinePoint],
+    signature: &LsagSignature,
+    linkability_flag: &str,
+) -> Result<String, String> {
+    encode_b64_json(
+        serde_json::json!({ "message": message }),
+        ring,
+        signature,
+        linkability_flag,
+    )
+}
+
+/// Encodes a signature of the binary `message` as [`encode_b64_lsag`] does, the message being
+/// hex encoded with `"messageEncoding": "hex"`.
+pub fn encode_b64_lsag_bytes(
+    message: &[u8],
+    ring: &[AffinePoint],
+    signature: &LsagSignature,
+    linkability_flag: &str,
+) -> Result<String, String> {
+    encode_b64_json(
+        serde_json::json!({ "message": hex::encode(message), "messageEncoding": "hex" }),
+        ring,
+        signature,
+        linkability_flag,
+    )
+}
+
+/// Adds the ring and the signature to the JSON holding the message, and base64 encodes it.
+fn encode_b64_json(
+    mut json: serde_json::Value,
+    ring: &[AffinePoint],
+    signature: &LsagSignature,
+    linkability_flag: &str,
+) -> Result<String, String> {
+    let ring = ring
+        .iter()
+        .map(|point| serialize_point(*point))
+        .collect::<Result<Vec<String>, String>>()?;
+    let fields = serde_json::json!({
+        "ring": ring,
+        "c": scalar_to_string(&signature.c0),
+        "responses": signature.responses.iter().map(scalar_to_string).collect::<Vec<_>>(),
+        "keyImage": serialize_point(signature.key_image)?,
+        "linkabilityFlag": linkability_flag,
+    });
+    if let (Some(json), serde_json::Value::Object(fields)) = (json.as_object_mut(), fields) {
+        json.extend(fields);
+    }
+    Ok(general_purpose::STANDARD.encode(json.to_string()))
+}
+
+/// Derives a scalar from the sha256 digest of `seed`.
+/// Meant for reproducible test and benchmark keys, never for real secrets.
+pub fn derive_scalar(seed: &[u8]) -> Scalar {
+    <Scalar as Reduce<U256>>::reduce_bytes(&Sha256::digest(seed))
+}
+
+/// Signs "Hello World" with a ring of `ring_size` keys derived from `seed`, and returns the
+/// base64-encoded LSAG. Everything is derived from `seed`, so the signature is reproducible.
+pub fn sample_b64_lsag(ring_size: usize, seed: u64) -> Result<String, String> {
+    sample_b64_lsag_with_message("Hello World", "linkability", ring_size, seed)
+}
+
+/// Signs `message` as [`sample_b64_lsag`] does, with the given linkability flag.
+pub fn sample_b64_lsag_with_message(
+    message: &str,
+    linkability_flag: &str,
+    ring_size: usize,
+    seed: u64,
+) -> Result<String, String> {
+    let (ring, signature) =
+        sample_signature(message.as_bytes(), linkability_flag, ring_size, seed)?;
+    encode_b64_lsag(message, &ring, &signature, linkability_flag)
+}
+
+/// Signs the binary `message` as [`sample_b64_lsag`] does, with the given linkability flag.
+pub fn sample_b64_lsag_with_bytes(
+    message: &[u8],
+    linkability_flag: &str,
+    ring_size: usize,
+    seed: u64,
+) -> Result<String, String> {
+    let (ring, signature) = sample_signature(message, linkability_flag, ring_size, seed)?;
+    encode_b64_lsag_bytes(message, &ring, &signature, linkability_flag)
+}
+
+/// Derives a ring from `seed` and signs `message` on behalf of one of its members.
+fn sample_signature(
+    message: &[u8],
+    linkability_flag: &str,
+    ring_size: usize,
+    seed: u64,
+) -> Result<(Vec<AffinePoint>, LsagSignature), String> {
+    let scalar = |label: &str, index: usize| {
+        derive_scalar(format!("{}/{}/{}", seed, label, index).as_bytes())
+    };
+    let private_keys: Vec<Scalar> = (0..ring_size).map(|i| scalar("key", i)).collect();
+    let ring: Vec<AffinePoint> = private_keys
+        .iter()
+        .map(|key| (ProjectivePoint::GENERATOR * key).to_affine())
+        .collect();
+    let responses: Vec<Scalar> = (0..ring_size).map(|i| scalar("response", i)).collect();
+    let signer_index = (seed % ring_size.max(1) as u64) as usize;
+
+    let signature = sign_lsag_digest(
+        &ring,
+        &Sha256::digest(message).into(),
+        signer_index,
+        *private_keys.get(signer_index).ok_or("Empty ring")?,
+        scalar("nonce", 0),
+        &responses,
+        Some(linkability_flag),
+    )?;
+    Ok((ring, signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsag_verifier::lsag_verifier::{verify_b64_lsag, verify_lsag_digest};
+
+    #[test]
+    fn signatures_verify_for_any_signer_position() {
+        for ring_size in [1, 2, 5] {
+            for seed in 0..ring_size as u64 {
+                let signature = sample_b64_lsag(ring_size, seed).unwrap();
+                assert!(verify_b64_lsag(signature).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn signs_only_with_the_key_of_the_signer() {
+        let keys = [derive_scalar(b"a"), derive_scalar(b"b")];
+        let ring: Vec<AffinePoint> = keys
+            .iter()
+            .map(|key| (ProjectivePoint::GENERATOR * key).to_affine())
+            .collect();
+        let responses = [derive_scalar(b"r0"), derive_scalar(b"r1")];
+        let digest = [7u8; 32];
+        let nonce = derive_scalar(b"nonce");
+
+        assert!(sign_lsag_digest(&ring, &digest, 0, keys[1], nonce, &responses, None).is_err());
+        let signature =
+            sign_lsag_digest(&ring, &digest, 1, keys[1], nonce, &responses, None).unwrap();
+        let verify = |digest: &[u8; 32]| {
+            verify_lsag_digest(
+                &ring,
+                digest,
+                signature.c0,
+                &signature.responses,
+                signature.key_image,
+                None,
+            )
+        };
+        assert!(verify(&digest));
+        assert!(!verify(&[8u8; 32]));
+    }
+}
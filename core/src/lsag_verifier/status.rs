@@ -0,0 +1,85 @@
+use ethabi::ethereum_types::U256;
+use ethabi::{encode, Token};
+
+/// Outcome of the verification of a base64-encoded LSAG.
+///
+/// The codes are committed on chain by the status guest, so existing values must never change.
+/// An input with several faults reports the first one found, in decoding order: the JSON, the
+/// ring and key image, the scalars, the ring size, then the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LsagStatus {
+    /// The signature is valid
+    Valid = 0,
+    /// The input is not the base64 encoding of a LSAG JSON
+    MalformedInput = 1,
+    /// A ring member or the key image is not a valid point
+    InvalidPoint = 2,
+    /// The challenge or a response is not a valid scalar
+    InvalidScalar = 3,
+    /// The message fields are missing, conflicting, or cannot be decoded
+    InvalidMessage = 4,
+    /// The ring is empty, or the number of responses differs from the ring size
+    RingSizeMismatch = 5,
+    /// The challenge chain does not close: the signature does not verify
+    InvalidSignature = 6,
+}
+
+// ABI encode the journal of the status guest, matching
+// `abi.encode(uint8 status, bytes32 inputDigest, bytes32 lsagDigest)` on chain
+pub fn abi_encode_status(
+    status: LsagStatus,
+    input_digest: &[u8; 32],
+    lsag_digest: &[u8; 32],
+) -> Vec<u8> {
+    encode(&[
+        Token::Uint(U256::from(status as u8)),
+        Token::FixedBytes(input_digest.to_vec()),
+        Token::FixedBytes(lsag_digest.to_vec()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsag_verifier::lsag_verifier::try_verify_b64_lsag;
+    use base64::engine::general_purpose;
+    use base64::Engine;
+
+    // publisher demo signature
+    const DEMO: &str = "eyJtZXNzYWdlIjoiSGVsbG8gV29ybGQiLCJyaW5nIjpbIjAyMjE4NjljYTNhZTMzYmUzYTczMjdlOWEwMjcyMjAzYWZhNzJjNTJhNTQ2MGNlYjlmNGE1MDkzMDUzMWJkOTI2YSIsIjAzNDI4ZTAyMGYxODRiNzBjYTkzMWE5MTA4NWFjMWMyMzM4MjdhNDFkODUxNmE0YjY0NTVlMjIxZTYzN2M0ZGUwZiIsIjAzNDM4ZmJmMzc3NmNjMjRlMjUzNTgyMjU0NGYxNGQ3YjA1N2Q4OTU3YzcyNjc2MDE4MDA1MmNkYTdiOGJhNmM4MyIsIjAzNTBjMWJkNjRjMzA4N2Y2NWY0ODE3MTdlZTRhNWJkZmJiYTRmMDYwMzE0OTkzZjFlMTVjMGRiMjk3NDhiOGRjMiJdLCJjIjoiM2M3ZDBhYzE4YjBlYWU4N2M1OTFjMGM5ZWRkOWE3ZDU3YjI5ZWUxZDhiNzZlYTFjOGM1NjAxMDQ3MGMwZDViMiIsInJlc3BvbnNlcyI6WyIxOWUzNGNjOTc5Y2E1YWMzYTk2MThkMGNlZThmYjdmNzRlMmY4MzA5MmY2ZDZmOTUyZTA3OWYxMzY1MmNlNjM2IiwiYjRkZGE5ZTc4YzA4OTliYjFjYmNkYTVjMjhiYWRiZjYwYWIzMDc1N2MyZjVhMWIxNWQwZDliNmQ1MzdhMTMwMSIsIjJhY2Q4ZWIzMzZhZjU5YzIwMTVhNDljMGJlMWZhZmE3Yzk0ODRmYWQ4YmY3MmFmYjZjYmIwYzgzMDhhOGUxODUiLCI1Y2IzNWY3OWVmYzBmODEwYTI0NTMxYjU0YWM0NThiNjZkMTZlNzNhMTdjOWEyY2IxYTkyN2QzYzI1YTNkMDY4Il0sImN1cnZlIjoie1wiY3VydmVcIjpcIlNFQ1AyNTZLMVwifSIsImtleUltYWdlIjoiMDJlN2ZmMzQ5MGVlN2RiMzM3NTBmZTlhNzA5MWQ4MmRjYTk1MmU2ZDIyYTdmZDRkZjk3ZDBjYmY4ZjdjYjQ2YWQyIiwibGlua2FiaWxpdHlGbGFnIjoibGlua2FiaWxpdHkiLCJjb25maWciOnsiaGFzaCI6InNoYTI1NiJ9fQ==";
+
+    /// The demo signature with the given fields replaced.
+    fn tampered(fields: &[(&str, serde_json::Value)]) -> String {
+        let decoded = general_purpose::STANDARD.decode(DEMO).unwrap();
+        let mut json: serde_json::Value = serde_json::from_slice(&decoded).unwrap();
+        for (field, value) in fields {
+            json[*field] = value.clone();
+        }
+        general_purpose::STANDARD.encode(json.to_string())
+    }
+
+    #[test]
+    fn the_first_fault_in_decoding_order_is_reported() {
+        // both a message and its digest
+        let message = ("messageDigest", serde_json::json!("00".repeat(32)));
+        let point = ("keyImage", serde_json::json!("02"));
+        let scalar = ("c", serde_json::json!("ff".repeat(32)));
+        let size = ("responses", serde_json::json!([]));
+
+        let status = |fields: &[(&str, serde_json::Value)]| try_verify_b64_lsag(&tampered(fields));
+        assert_eq!(status(&[message.clone()]), Err(LsagStatus::InvalidMessage));
+        assert_eq!(
+            status(&[message.clone(), size.clone()]),
+            Err(LsagStatus::RingSizeMismatch)
+        );
+        assert_eq!(
+            status(&[message.clone(), size.clone(), scalar.clone()]),
+            Err(LsagStatus::InvalidScalar)
+        );
+        assert_eq!(
+            status(&[message, size, scalar, point]),
+            Err(LsagStatus::InvalidPoint)
+        );
+    }
+}
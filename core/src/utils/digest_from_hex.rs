@@ -0,0 +1,8 @@
+/// Parses a 32-byte digest from a hexadecimal string, optionally `0x`-prefixed.
+pub fn digest_from_hex(hex_string: &str) -> Result<[u8; 32], String> {
+    let hex_string = hex_string.strip_prefix("0x").unwrap_or(hex_string);
+    let bytes = hex::decode(hex_string).map_err(|_| "Invalid hexadecimal string".to_string())?;
+    bytes
+        .try_into()
+        .map_err(|_| "Digest must be 32 bytes".to_string())
+}
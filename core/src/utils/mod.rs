@@ -1,3 +1,4 @@
+pub mod digest_from_hex;
 pub mod hash_to_secp256k1;
 pub mod hex_to_decimal;
 pub mod scalar_from_hex;
@@ -5,11 +6,15 @@ pub mod scalar_to_string;
 pub mod serialize_point;
 pub mod serialize_ring;
 pub mod sha256;
-pub mod test_utils;
 
+pub use digest_from_hex::digest_from_hex;
 pub use hash_to_secp256k1::hash_to_secp256k1;
 pub use hex_to_decimal::hex_to_decimal;
 pub use scalar_from_hex::scalar_from_hex;
-pub use serialize_point::{deserialize_point, serialize_point};
-pub use serialize_ring::{deserialize_ring, serialize_ring};
+pub use serialize_point::{
+    deserialize_coordinates, deserialize_point, point_coordinates, serialize_point, PointEncoding,
+};
+pub use serialize_ring::{
+    decode_ring, deserialize_coordinates_ring, deserialize_ring, serialize_ring,
+};
 pub use sha256::sha_256;
@@ -0,0 +1,40 @@
+use k256::elliptic_curve::PrimeField;
+use k256::Scalar;
+pub fn scalar_from_hex(hex_string: &str) -> Result<Scalar, String> {
+    let hex_string = hex_string.trim_start_matches("0x");
+
+    // Decode the hex string into bytes
+    let hex_bytes =
+        hex::decode(hex_string).map_err(|_| "Invalid hexadecimal string".to_string())?;
+
+    // Prepare a 32-byte array
+    let mut scalar_bytes = [0u8; 32];
+    let len = hex_bytes.len();
+    if len > 32 {
+        return Err("Scalar does not fit in 32 bytes".to_string());
+    }
+
+    // Copy the bytes into the scalar_bytes array, padding with zeros on the left if necessary
+    scalar_bytes[32 - len..].copy_from_slice(&hex_bytes);
+
+    // Create Scalar from the 32-byte array, rejecting values not reduced modulo the curve order
+    Option::from(Scalar::from_repr(scalar_bytes.into()))
+        .ok_or_else(|| "Scalar is not reduced modulo the curve order".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_scalars_without_panicking() {
+        assert_eq!(scalar_from_hex("0x01").unwrap(), Scalar::ONE);
+        assert!(scalar_from_hex(&"01".repeat(33)).is_err());
+        // the curve order
+        assert!(scalar_from_hex(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+        )
+        .is_err());
+        assert!(scalar_from_hex("zz").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use hex::{self, FromHex};
+use k256::elliptic_curve::sec1::FromEncodedPoint;
+use k256::{elliptic_curve::sec1::ToEncodedPoint, AffinePoint, EncodedPoint};
+use num_bigint::BigUint;
+use num_traits::Num;
+use serde::Deserialize;
+
+/// A point in any of the encodings accepted for ring members and key images.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PointEncoding {
+    /// Compressed (33 bytes) or uncompressed (65 bytes) hexadecimal string, optionally `0x`-prefixed
+    Hex(String),
+    /// Decimal affine coordinates
+    Coordinates { x: String, y: String },
+}
+
+impl PointEncoding {
+    /// Decodes the point, whatever its encoding
+    pub fn decode(&self) -> Result<AffinePoint, String> {
+        match self {
+            PointEncoding::Hex(hex_str) => deserialize_point(hex_str),
+            PointEncoding::Coordinates { x, y } => deserialize_coordinates(x, y),
+        }
+    }
+}
+
+/// Serializes an AffinePoint to a compressed hexadecimal string
+pub fn serialize_point(point: AffinePoint) -> Result<String, String> {
+    let encoded = point.to_encoded_point(false); // Uncompressed
+    let x_bytes = encoded
+        .x()
+        .ok_or_else(|| "x coordinate missing".to_string())?;
+    let y_bytes = encoded
+        .y()
+        .ok_or_else(|| "y coordinate missing".to_string())?;
+    let x_hex_padded = format!("{:0>64}", hex::encode(x_bytes));
+    let prefix = if (y_bytes
+        .last()
+        .ok_or_else(|| "y coordinate is empty".to_string())?
+        & 1)
+        == 0
+    {
+        "02"
+    } else {
+        "03"
+    };
+    Ok(format!("{}{}", prefix, x_hex_padded))
+}
+
+/// Returns the big-endian affine coordinates of a point, as ABI encoded in the journal
+pub fn point_coordinates(point: &AffinePoint) -> ([u8; 32], [u8; 32]) {
+    let encoded = point.to_encoded_point(false);
+    let mut x = [0u8; 32];
+    let mut y = [0u8; 32];
+    // the identity has no coordinates, it cannot be decoded from a signature
+    if let (Some(x_bytes), Some(y_bytes)) = (encoded.x(), encoded.y()) {
+        x.copy_from_slice(x_bytes);
+        y.copy_from_slice(y_bytes);
+    }
+    (x, y)
+}
+
+/// Deserialize a compressed or uncompressed hexadecimal string to an AffinePoint.
+/// A `0x` prefix is accepted.
+pub fn deserialize_point(hex_str: &str) -> Result<AffinePoint, String> {
+    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+    let bytes = Vec::from_hex(hex_str).map_err(|_| "Invalid hexadecimal string".to_string())?;
+    if bytes.len() != 33 && bytes.len() != 65 {
+        return Err("Invalid length for a compressed or uncompressed point".to_string());
+    }
+    let encoded_point =
+        EncodedPoint::from_bytes(&bytes).map_err(|_| "Invalid point encoding".to_string())?;
+    let affine_point = AffinePoint::from_encoded_point(&encoded_point);
+    if affine_point.is_some().into() {
+        Ok(affine_point.unwrap())
+    } else {
+        Err("Failed to parse AffinePoint from encoded point".to_string())
+    }
+}
+
+/// Deserialize decimal affine coordinates to an AffinePoint.
+/// Coordinates must be reduced modulo the field prime and lie on the curve.
+pub fn deserialize_coordinates(x: &str, y: &str) -> Result<AffinePoint, String> {
+    let x_bytes = decimal_to_32_bytes(x)?;
+    let y_bytes = decimal_to_32_bytes(y)?;
+    let encoded_point =
+        EncodedPoint::from_affine_coordinates(&x_bytes.into(), &y_bytes.into(), false);
+    let affine_point = AffinePoint::from_encoded_point(&encoded_point);
+    if affine_point.is_some().into() {
+        Ok(affine_point.unwrap())
+    } else {
+        Err("Coordinates are not a point on secp256k1".to_string())
+    }
+}
+
+fn decimal_to_32_bytes(decimal: &str) -> Result<[u8; 32], String> {
+    let value = BigUint::from_str_radix(decimal, 10)
+        .map_err(|_| "Invalid decimal coordinate".to_string())?;
+    let value_bytes = value.to_bytes_be();
+    if value_bytes.len() > 32 {
+        return Err("Coordinate does not fit in 32 bytes".to_string());
+    }
+    let mut bytes = [0u8; 32];
+    bytes[32 - value_bytes.len()..].copy_from_slice(&value_bytes);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPRESSED: &str = "0221869ca3ae33be3a7327e9a0272203afa72c52a5460ceb9f4a50930531bd926a";
+    const UNCOMPRESSED: &str = "0421869ca3ae33be3a7327e9a0272203afa72c52a5460ceb9f4a50930531bd926a2c9535b5f71409cf334b639de2d39074ea35c3057857e32ca5487f5d3c68d982";
+    const X: &str = "15164162595175125008547705889856181828932143716710538299042410382956573856362";
+    const Y: &str = "20165396248642806335661137158563863822683438728408180285542980607824890485122";
+
+    #[test]
+    fn all_encodings_decode_to_the_same_point() {
+        let expected = deserialize_point(COMPRESSED).unwrap();
+        assert_eq!(deserialize_point(UNCOMPRESSED).unwrap(), expected);
+        assert_eq!(
+            deserialize_point(&format!("0x{}", COMPRESSED)).unwrap(),
+            expected
+        );
+        assert_eq!(
+            deserialize_point(&format!("0x{}", UNCOMPRESSED)).unwrap(),
+            expected
+        );
+        assert_eq!(deserialize_coordinates(X, Y).unwrap(), expected);
+        assert_eq!(serialize_point(expected).unwrap(), COMPRESSED);
+        let (x, y) = point_coordinates(&expected);
+        assert_eq!(hex::encode(x) + &hex::encode(y), &UNCOMPRESSED[2..]);
+    }
+
+    #[test]
+    fn point_encoding_parses_strings_and_coordinates() {
+        let hex: PointEncoding = serde_json::from_str(&format!("\"{}\"", UNCOMPRESSED)).unwrap();
+        let coordinates: PointEncoding =
+            serde_json::from_str(&format!("{{\"x\":\"{}\",\"y\":\"{}\"}}", X, Y)).unwrap();
+        assert_eq!(hex, PointEncoding::Hex(UNCOMPRESSED.to_string()));
+        assert_eq!(hex.decode().unwrap(), coordinates.decode().unwrap());
+    }
+
+    #[test]
+    fn rejects_invalid_hex_points() {
+        // wrong length
+        assert!(deserialize_point(&COMPRESSED[..64]).is_err());
+        // uncompressed prefix on a compressed length
+        assert!(deserialize_point(&format!("04{}", &COMPRESSED[2..])).is_err());
+        // y does not match x
+        let mut tampered = UNCOMPRESSED.to_string();
+        tampered.replace_range(129.., "3");
+        assert!(deserialize_point(&tampered).is_err());
+        assert!(deserialize_point("zz").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_coordinates() {
+        // off curve
+        assert!(deserialize_coordinates(X, "1").is_err());
+        // (1, y) is on the curve, but (1 + p, y) is not a canonical encoding of it
+        let y = "29896722852569046015560700294576055776214335159245303116488692907525646231534";
+        let one_plus_p =
+            "115792089237316195423570985008687907853269984665640564039457584007908834671664";
+        assert!(deserialize_coordinates("1", y).is_ok());
+        assert!(deserialize_coordinates(one_plus_p, y).is_err());
+        assert!(deserialize_coordinates("0x12", Y).is_err());
+        assert!(deserialize_coordinates(&"9".repeat(80), Y).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use k256::AffinePoint;
+
+use super::serialize_point::{
+    deserialize_coordinates, deserialize_point, serialize_point, PointEncoding,
+};
+
+/// Serializes a ring of points into a string.
+/// converts the points to strings and concatenates them.
+pub fn serialize_ring(ring: &[AffinePoint]) -> Result<String, String> {
+    let mut serialized = String::new();
+    for point in ring {
+        let point_str =
+            serialize_point(*point).map_err(|e| format!("Failed to serialize point: {}", e))?;
+        serialized.push_str(&point_str);
+    }
+    Ok(serialized)
+}
+
+pub fn deserialize_ring(ring: &[String]) -> Result<Vec<AffinePoint>, String> {
+    let mut deserialized_points = Vec::new();
+
+    for point in ring {
+        let deserialized_point = deserialize_point(point)?;
+        deserialized_points.push(deserialized_point);
+    }
+
+    Ok(deserialized_points)
+}
+
+/// Deserializes a ring given as decimal (x, y) coordinate pairs.
+pub fn deserialize_coordinates_ring(ring: &[(&str, &str)]) -> Result<Vec<AffinePoint>, String> {
+    ring.iter()
+        .map(|(x, y)| deserialize_coordinates(x, y))
+        .collect()
+}
+
+/// Decodes a ring whose members may use any supported point encoding.
+pub fn decode_ring(ring: &[PointEncoding]) -> Result<Vec<AffinePoint>, String> {
+    ring.iter().map(PointEncoding::decode).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinates_ring_matches_compressed_ring() {
+        let compressed = vec![
+            "0221869ca3ae33be3a7327e9a0272203afa72c52a5460ceb9f4a50930531bd926a".to_string(),
+            "03428e020f184b70ca931a91085ac1c233827a41d8516a4b6455e221e637c4de0f".to_string(),
+        ];
+        let coordinates = [
+            (
+                "15164162595175125008547705889856181828932143716710538299042410382956573856362",
+                "20165396248642806335661137158563863822683438728408180285542980607824890485122",
+            ),
+            (
+                "30103554500144535254965021336757008479704861502777924021458799636567575289359",
+                "52090609727678693574435399254703833889410700116234244177206170117175907888773",
+            ),
+        ];
+        let ring = deserialize_ring(&compressed).unwrap();
+        assert_eq!(deserialize_coordinates_ring(&coordinates).unwrap(), ring);
+        assert_eq!(serialize_ring(&ring).unwrap(), compressed.concat());
+    }
+}
@@ -0,0 +1,203 @@
+// Golden test vectors: valid signatures with their expected minimal LSAG digest, and systematic
+// tampering of each of their fields. The vectors live in tests/vectors/: `reference/` holds the
+// signatures of the reference signing library, written by `reference/generate.mjs`, and
+// `self_signed/` an extra set signed by the lsag-core signer, written by
+// `examples/golden_vectors.rs`.
+
+use std::fs;
+use std::path::PathBuf;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use lsag_core::lsag_verifier::conversion::convert_string_to_json;
+use lsag_core::lsag_verifier::minimal::to_minimal_lsag_digest;
+use lsag_core::lsag_verifier::{try_verify_b64_lsag, verify_b64_lsag};
+use lsag_core::utils::decode_ring;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The vector sets, and the signer each of their vectors must name.
+const SETS: [(&str, &str); 2] = [
+    ("reference", "alicesring-lsag"),
+    ("self_signed", "lsag-core"),
+];
+
+#[derive(Deserialize, Debug)]
+struct Vector {
+    description: String,
+    signature: String,
+    signer: String,
+    minimal_lsag_digest: String,
+}
+
+/// The vectors of `set`, keyed by file name.
+fn vector_set(set: &str) -> Vec<(String, Vector)> {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/vectors")
+        .join(set);
+    let mut vectors: Vec<(String, Vector)> = fs::read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .map(|path| {
+            let vector = serde_json::from_slice(&fs::read(&path).unwrap())
+                .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            let name = path.file_name().unwrap().to_string_lossy();
+            (format!("{}/{}", set, name), vector)
+        })
+        .collect();
+    vectors.sort_by(|a, b| a.0.cmp(&b.0));
+    assert!(!vectors.is_empty(), "no {} test vectors found", set);
+    vectors
+}
+
+fn vectors() -> Vec<(String, Vector)> {
+    SETS.iter().flat_map(|(set, _)| vector_set(set)).collect()
+}
+
+fn decode_json(b64_signature: &str) -> Value {
+    serde_json::from_slice(&general_purpose::STANDARD.decode(b64_signature).unwrap()).unwrap()
+}
+
+fn encode_json(json: &Value) -> String {
+    general_purpose::STANDARD.encode(json.to_string())
+}
+
+/// Flips `bit` of `bytes`, counting from the most significant bit of the first byte.
+fn flip(bytes: &[u8], bit: usize) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    bytes[bit / 8] ^= 0x80 >> (bit % 8);
+    bytes
+}
+
+/// Flips the first and the last bit of a hex field, e.g. the prefix and the low bit of x for a
+/// compressed point.
+fn tampered_hex(value: &Value) -> Vec<Value> {
+    let bytes = hex::decode(value.as_str().unwrap()).unwrap();
+    [0, bytes.len() * 8 - 1]
+        .iter()
+        .map(|bit| Value::String(hex::encode(flip(&bytes, *bit))))
+        .collect()
+}
+
+/// Every tampered copy of the signature, with a label of what was tampered with.
+fn tampered_signatures(json: &Value) -> Vec<(String, Value)> {
+    let mut tampered = Vec::new();
+    let mut push = |label: String, field: &str, index: Option<usize>, value: Value| {
+        let mut copy = json.clone();
+        match index {
+            Some(index) => copy[field][index] = value,
+            None => copy[field] = value,
+        }
+        tampered.push((label, copy));
+    };
+
+    for value in tampered_hex(&json["c"]) {
+        push("c".into(), "c", None, value);
+    }
+    for field in ["responses", "ring"] {
+        for (index, element) in json[field].as_array().unwrap().iter().enumerate() {
+            for value in tampered_hex(element) {
+                push(format!("{}[{}]", field, index), field, Some(index), value);
+            }
+        }
+    }
+    for value in tampered_hex(&json["keyImage"]) {
+        push("keyImage".into(), "keyImage", None, value);
+    }
+
+    // messages are hex encoded once tampered, as a flipped bit may break the utf-8 encoding
+    let message = json["message"].as_str().unwrap().as_bytes();
+    let tampered_messages = match message.len() {
+        // there is no bit to flip in an empty message, a zero byte is appended instead
+        0 => vec![vec![0]],
+        len => vec![flip(message, 0), flip(message, len * 8 - 1)],
+    };
+    for message in tampered_messages {
+        let mut copy = json.clone();
+        copy["message"] = Value::String(hex::encode(message));
+        copy["messageEncoding"] = Value::String("hex".into());
+        tampered.push(("message".into(), copy));
+    }
+
+    let flag = json["linkabilityFlag"].as_str().unwrap();
+    let tampered_flags = match flag.len() {
+        0 => vec!["\0".to_string()],
+        // flipping the low bits keeps ascii flags valid utf-8
+        len => [7, len * 8 - 1]
+            .iter()
+            .map(|bit| String::from_utf8_lossy(&flip(flag.as_bytes(), *bit)).into_owned())
+            .collect(),
+    };
+    for flag in tampered_flags {
+        let mut copy = json.clone();
+        copy["linkabilityFlag"] = Value::String(flag);
+        tampered.push(("linkabilityFlag".into(), copy));
+    }
+    tampered
+}
+
+#[test]
+fn vectors_are_labelled_with_their_signer() {
+    for (set, signer) in SETS {
+        for (name, vector) in vector_set(set) {
+            assert_eq!(vector.signer, signer, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn vectors_verify_to_their_minimal_lsag_digest() {
+    for (name, vector) in vectors() {
+        let expected = hex::decode(&vector.minimal_lsag_digest).unwrap();
+        let digest = verify_b64_lsag(vector.signature.clone())
+            .unwrap_or_else(|| panic!("{} ({}) does not verify", name, vector.description));
+        assert_eq!(digest.to_vec(), expected, "{}", name);
+
+        let json = general_purpose::STANDARD.decode(&vector.signature).unwrap();
+        let lsag = convert_string_to_json(std::str::from_utf8(&json).unwrap()).unwrap();
+        let digest = to_minimal_lsag_digest(
+            &decode_ring(&lsag.ring).unwrap(),
+            lsag.message.unwrap().as_bytes(),
+            lsag.keyImage.decode().unwrap(),
+            Some(&lsag.linkabilityFlag),
+        );
+        assert_eq!(digest.to_vec(), expected, "{}", name);
+    }
+}
+
+#[test]
+fn tampered_vectors_are_rejected() {
+    for (name, vector) in vectors() {
+        let json = decode_json(&vector.signature);
+        // the tampering helpers expect canonical hex fields and a utf-8 message
+        assert!(json.get("messageEncoding").is_none(), "{}", name);
+        let tampered = tampered_signatures(&json);
+        assert!(tampered.len() > 4 * json["ring"].as_array().unwrap().len());
+        for (label, copy) in tampered {
+            assert!(
+                try_verify_b64_lsag(&encode_json(&copy)).is_err(),
+                "{}: tampering with {} is accepted",
+                name,
+                label
+            );
+        }
+    }
+}
+
+#[test]
+fn re_encoded_vectors_still_verify() {
+    // the tampered copies are re-encoded, make sure the encoding alone is not what rejects them
+    for (name, vector) in vectors() {
+        let json = decode_json(&vector.signature);
+        let mut hex_message = json.clone();
+        hex_message["message"] = Value::String(hex::encode(json["message"].as_str().unwrap()));
+        hex_message["messageEncoding"] = Value::String("hex".into());
+        for copy in [json, hex_message] {
+            assert!(try_verify_b64_lsag(&encode_json(&copy)).is_ok(), "{}", name);
+        }
+    }
+}
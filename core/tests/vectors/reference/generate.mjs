@@ -0,0 +1,58 @@
+// Signs the reference test vectors with the reference TypeScript LSAG library, and writes them
+// next to this script. Run from this directory:
+//
+//     npm install --no-save @cypher-laboratory/alicesring-lsag
+//     node generate.mjs
+//     cargo run -p lsag-core --example golden_vectors
+//
+// The last command fills in the expected minimal LSAG digest of each new vector. Keys are
+// derived from fixed seeds, but the library draws its own nonces: regenerating rewrites the
+// signatures, so only add or replace the vectors that changed.
+
+import { createHash } from "node:crypto";
+import { writeFileSync } from "node:fs";
+import { Curve, CurveName, RingSignature } from "@cypher-laboratory/alicesring-lsag";
+
+const LOREM =
+  "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+// [file name, message, linkability flag, ring size, seed], as the self-signed vectors
+const VECTORS = [
+  ["ring_1", "Hello World", "linkability", 1, 1],
+  ["ring_2_empty_message", "", "linkability", 2, 2],
+  ["ring_3_empty_flag", "vote:2026-10-19", "", 3, 3],
+  ["ring_5_unicode_message", "héllo wörld ✓", "poll-42", 5, 5],
+  ["ring_8_long_message", LOREM, "linkability", 8, 8],
+  ["ring_12_flag_with_spaces", "Hello World", "a flag with spaces", 12, 12],
+  ["ring_16", "Hello World", "linkability", 16, 16],
+  ["ring_32", "Hello World", "linkability", 32, 32],
+];
+
+const curve = new Curve(CurveName.SECP256K1);
+
+// a private key derived from the sha256 digest of its label, never zero
+function privateKey(label) {
+  const digest = BigInt("0x" + createHash("sha256").update(label).digest("hex"));
+  return (digest % (curve.N - 1n)) + 1n;
+}
+
+for (const [name, message, linkabilityFlag, ringSize, seed] of VECTORS) {
+  const signer = privateKey(`${seed}/signer`);
+  // the library adds the signer's key to the ring it is given
+  const ring = [];
+  for (let i = 1; i < ringSize; i++) {
+    ring.push(curve.GtoPoint().mult(privateKey(`${seed}/member/${i}`)));
+  }
+  const signature = RingSignature.sign(ring, signer, message, curve, linkabilityFlag);
+  if (!signature.verify() || signature.getRing().length !== ringSize) {
+    throw new Error(`${name}: the library produced an unexpected signature`);
+  }
+
+  const vector = {
+    description: `ring of ${ringSize} members, ${Buffer.byteLength(message)} byte message, flag ${JSON.stringify(linkabilityFlag)}`,
+    signature: signature.toBase64(),
+    signer: "alicesring-lsag",
+  };
+  writeFileSync(`${name}.json`, JSON.stringify(vector, null, 2) + "\n");
+  console.log(`wrote ${name}.json`);
+}
@@ -0,0 +1,113 @@
+// Records the cycles of the `lsag_verifier` guest for ring signatures of growing ring sizes,
+// by executing it without proving.
+//
+//     cargo bench -p methods --bench cycles
+//
+// The results are written as JSON to target/lsag-bench/cycles.json, or to LSAG_BENCH_OUTPUT.
+// When that file already holds the results of a previous version, the change of the user
+// cycles of each ring size is printed before it is overwritten. LSAG_BENCH_RING_SIZES
+// restricts the run to a comma-separated list of ring sizes.
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use lsag_core::lsag_verifier::signer::sample_b64_lsag;
+use methods::{LSAG_VERIFIER_ELF, LSAG_VERIFIER_ID};
+use risc0_zkvm::{default_executor, sha::Digest, ExecutorEnv};
+use serde::{Deserialize, Serialize};
+
+const RING_SIZES: [usize; 6] = [2, 8, 32, 128, 512, 1024];
+
+/// The cycles of one execution of the guest.
+#[derive(Serialize, Deserialize, Debug)]
+struct CycleReport {
+    ring_size: usize,
+    user_cycles: u64,
+    /// The cycles of all segments, padded to their power of two size
+    total_cycles: u64,
+    segments: usize,
+    /// The user cycles of each verification phase, as reported by the guest
+    phases: BTreeMap<String, u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct BenchResults {
+    image_id: String,
+    reports: Vec<CycleReport>,
+}
+
+fn execute(ring_size: usize) -> CycleReport {
+    let input = sample_b64_lsag(ring_size, 0).expect("failed to sign the benchmark input");
+    let mut guest_stdout = Vec::new();
+    let env = ExecutorEnv::builder()
+        .env_var("LSAG_CYCLES", "1")
+        .stdout(&mut guest_stdout)
+        .write(&input)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = default_executor()
+        .execute(env, LSAG_VERIFIER_ELF)
+        .expect("guest execution failed");
+
+    let phases = String::from_utf8_lossy(&guest_stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix("lsag-cycles "))
+        .filter_map(|phase| {
+            let (name, cycles) = phase.split_once(' ')?;
+            Some((name.to_string(), cycles.parse().ok()?))
+        })
+        .collect();
+    CycleReport {
+        ring_size,
+        user_cycles: session.cycles(),
+        total_cycles: session.segments.iter().map(|s| 1u64 << s.po2).sum(),
+        segments: session.segments.len(),
+        phases,
+    }
+}
+
+fn main() {
+    let ring_sizes: Vec<usize> = match std::env::var("LSAG_BENCH_RING_SIZES") {
+        Ok(sizes) => sizes
+            .split(',')
+            .map(|size| size.trim().parse().expect("invalid LSAG_BENCH_RING_SIZES"))
+            .collect(),
+        Err(_) => RING_SIZES.to_vec(),
+    };
+    let output = std::env::var("LSAG_BENCH_OUTPUT").map_or_else(
+        |_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target/lsag-bench/cycles.json"),
+        PathBuf::from,
+    );
+    let previous: Option<BenchResults> = fs::read(&output)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+
+    let mut reports = Vec::new();
+    for ring_size in ring_sizes {
+        let report = execute(ring_size);
+        let change = previous
+            .iter()
+            .flat_map(|results| &results.reports)
+            .find(|previous| previous.ring_size == ring_size)
+            .map(|previous| {
+                let delta = report.user_cycles as f64 / previous.user_cycles as f64 - 1.0;
+                format!(" ({:+.2}%)", delta * 100.0)
+            })
+            .unwrap_or_default();
+        println!(
+            "ring size {:>5}: {:>12} user cycles{}, {:>12} total cycles, {} segments",
+            ring_size, report.user_cycles, change, report.total_cycles, report.segments
+        );
+        reports.push(report);
+    }
+
+    let results = BenchResults {
+        image_id: Digest::from(LSAG_VERIFIER_ID).to_string(),
+        reports,
+    };
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(&output, serde_json::to_string_pretty(&results).unwrap()).unwrap();
+    println!("results written to {}", output.display());
+}
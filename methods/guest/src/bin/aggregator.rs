@@ -0,0 +1,14 @@
+use lsag_core::lsag_verifier::aggregation::abi_encode_aggregation;
+use risc0_zkvm::guest::env;
+use risc0_zkvm::sha::Digest;
+fn main() {
+    // image ID of the lsag_verifier guest, and the journal of each receipt to aggregate
+    let (verifier_image_id, journals): (Digest, Vec<[u8; 32]>) = env::read();
+    assert!(!journals.is_empty(), "no receipt to aggregate");
+    for journal in &journals {
+        // adds the lsag_verifier receipt as an assumption, resolved by the host prover
+        env::verify(verifier_image_id, &journal[..]).unwrap();
+    }
+    let image_id: [u8; 32] = verifier_image_id.into();
+    env::commit_slice(&abi_encode_aggregation(&image_id, &journals));
+}
@@ -0,0 +1,8 @@
+use lsag_core::lsag_verifier::chunked::verify_chunk;
+use risc0_zkvm::guest::env;
+fn main() {
+    // signature, first ring member of the chunk, number of members, challenge of the first member
+    let (input, start, len, c_start): (String, u32, u32, Option<[u8; 32]>) = env::read();
+    let journal = verify_chunk(&input, start, len, c_start).unwrap();
+    env::commit(&journal);
+}
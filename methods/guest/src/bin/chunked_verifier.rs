@@ -0,0 +1,16 @@
+use lsag_core::lsag_verifier::chunked::{verify_chunked_b64_lsag, ChunkJournal};
+use risc0_zkvm::guest::env;
+use risc0_zkvm::sha::Digest;
+fn main() {
+    // image ID of the lsag_chunk guest, signature, and the journal of each chunk receipt
+    let (chunk_image_id, input, chunks): (Digest, String, Vec<ChunkJournal>) = env::read();
+    for chunk in &chunks {
+        // adds the lsag_chunk receipt as an assumption, resolved by the host prover
+        env::verify(chunk_image_id, &risc0_zkvm::serde::to_vec(chunk).unwrap()).unwrap();
+    }
+    let result = verify_chunked_b64_lsag(&input, &chunks);
+    assert!(result.is_some(), "ring signature verification failed");
+    let image_id: [u8; 32] = chunk_image_id.into();
+    env::commit_slice(&image_id);
+    env::commit_slice(&result.unwrap());
+}
@@ -0,0 +1,14 @@
+use lsag_core::lsag_verifier::endorsement::verify_b64_endorsement;
+use risc0_zkvm::guest::env;
+use risc0_zkvm::sha::Digest;
+fn main() {
+    // image ID and journal of the endorsed receipt, and the signature over the journal digest
+    let (endorsed_image_id, endorsed_journal, input): (Digest, Vec<u8>, String) = env::read();
+    // adds the endorsed receipt as an assumption, resolved by the host prover
+    env::verify(endorsed_image_id, &endorsed_journal).unwrap();
+    let result = verify_b64_endorsement(input, &endorsed_journal);
+    assert!(result.is_some(), "ring signature verification failed");
+    let image_id: [u8; 32] = endorsed_image_id.into();
+    env::commit_slice(&image_id);
+    env::commit_slice(&result.unwrap());
+}
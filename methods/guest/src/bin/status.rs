@@ -0,0 +1,14 @@
+use lsag_core::lsag_verifier::status::{abi_encode_status, LsagStatus};
+use lsag_core::lsag_verifier::try_verify_b64_lsag;
+use risc0_zkvm::guest::env;
+use sha2::{Digest, Sha256};
+fn main() {
+    let input: String = env::read();
+    // commits the outcome instead of panicking, so that invalid signatures can be proven too
+    let input_digest: [u8; 32] = Sha256::digest(input.as_bytes()).into();
+    let (status, lsag_digest) = match try_verify_b64_lsag(&input) {
+        Ok(digest) => (LsagStatus::Valid, digest),
+        Err(status) => (status, [0u8; 32]),
+    };
+    env::commit_slice(&abi_encode_status(status, &input_digest, &lsag_digest));
+}
@@ -1,8 +1,27 @@
-use guests::lsag_verifier::lsag_verifier::verify_b64_lsag;
+use lsag_core::lsag_verifier::lsag_verifier::{trace_b64_lsag, try_verify_b64_lsag_with_phases};
 use risc0_zkvm::guest::env;
 fn main() {
     let input: String = env::read();
-    let result = verify_b64_lsag(input.clone());
-    assert!(result != None, "ring signature verification failed");
+    // the host only forwards LSAG_TRACE in dev mode
+    if std::env::var("LSAG_TRACE").is_ok() {
+        match trace_b64_lsag(&input) {
+            Ok(trace) => println!("{}", serde_json::to_string_pretty(&trace).unwrap()),
+            Err(e) => println!("LSAG trace failed: {}", e),
+        }
+    }
+    // the host only sets LSAG_CYCLES for dry runs, to report the cycles of each phase
+    let report_cycles = std::env::var("LSAG_CYCLES").is_ok();
+    let mut phase_start = env::cycle_count();
+    let result = try_verify_b64_lsag_with_phases(&input, |phase| {
+        if report_cycles {
+            println!(
+                "lsag-cycles {} {}",
+                phase.name(),
+                env::cycle_count() - phase_start
+            );
+            phase_start = env::cycle_count();
+        }
+    });
+    assert!(result.is_ok(), "ring signature verification failed");
     env::commit_slice(&result.unwrap());
 }
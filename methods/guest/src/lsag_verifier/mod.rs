@@ -1,5 +0,0 @@
-pub mod compute;
-pub mod conversion;
-pub mod lsag_verifier;
-pub mod minimal;
-pub use lsag_verifier::verify_b64_lsag;
@@ -0,0 +1,47 @@
+// The endorsement guest, executed with the endorsed receipt as an assumption: it must commit the
+// image ID of the endorsed computation followed by the digest of the ring signature data, and
+// reject a signature of another journal.
+
+use lsag_core::lsag_verifier::endorsement::verify_b64_endorsement;
+use lsag_core::lsag_verifier::signer::sample_b64_lsag_with_bytes;
+use methods::{LSAG_ENDORSEMENT_ELF, LSAG_VERIFIER_ID};
+use risc0_zkvm::sha::Digest;
+use risc0_zkvm::{default_executor, ExecutorEnv, FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+use sha2::{Digest as _, Sha256};
+
+/// Executes the endorsement guest on `signature`, endorsing `journal` from the verifier image.
+/// Returns `None` if the guest panics.
+fn execute(journal: &[u8], signature: &str) -> Option<Vec<u8>> {
+    let image_id = Digest::from(LSAG_VERIFIER_ID);
+    // the executor only looks the assumption up by its claim
+    let claim = ReceiptClaim::ok(image_id, journal.to_vec());
+    let endorsed = Receipt::new(
+        InnerReceipt::Fake(FakeReceipt::new(claim)),
+        journal.to_vec(),
+    );
+    let env = ExecutorEnv::builder()
+        .add_assumption(endorsed)
+        .write(&(image_id, journal.to_vec(), signature.to_string()))
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = default_executor().execute(env, LSAG_ENDORSEMENT_ELF).ok()?;
+    Some(session.journal.bytes)
+}
+
+#[test]
+fn guest_commits_the_endorsed_image_and_digest() {
+    // RISC0_SKIP_BUILD leaves the guest unbuilt
+    if LSAG_ENDORSEMENT_ELF.is_empty() {
+        return;
+    }
+    let journal = b"journal of the endorsed receipt";
+    let signature =
+        sample_b64_lsag_with_bytes(&Sha256::digest(journal), "endorsement", 3, 1).unwrap();
+    let digest = verify_b64_endorsement(signature.clone(), journal).unwrap();
+
+    let committed = execute(journal, &signature).unwrap();
+    let image_id: [u8; 32] = Digest::from(LSAG_VERIFIER_ID).into();
+    assert_eq!(committed, [image_id, digest].concat());
+    assert!(execute(b"another journal", &signature).is_none());
+}
@@ -0,0 +1,152 @@
+// Property-based tests tying the signer, the native verifier and the guest together: random
+// rings, signers, messages and flags are signed with the lsag-core signer, verified natively,
+// corrupted, and verified in the executor, whose journal must match the host digest.
+
+use k256::{AffinePoint, ProjectivePoint, Scalar};
+use lsag_core::lsag_verifier::lsag_verifier::verify_lsag;
+use lsag_core::lsag_verifier::minimal::to_minimal_lsag_digest;
+use lsag_core::lsag_verifier::signer::{
+    derive_scalar, encode_b64_lsag, sign_lsag_digest, LsagSignature,
+};
+use methods::LSAG_VERIFIER_ELF;
+use proptest::prelude::*;
+use proptest::sample::Index;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+use sha2::{Digest, Sha256};
+
+/// A signed ring signature and what it signs.
+#[derive(Debug)]
+struct Signed {
+    ring: Vec<AffinePoint>,
+    message: String,
+    linkability_flag: String,
+    signature: LsagSignature,
+}
+
+impl Signed {
+    fn verify(&self) -> bool {
+        verify_lsag(
+            &self.ring,
+            self.message.as_bytes(),
+            self.signature.c0,
+            &self.signature.responses,
+            self.signature.key_image,
+            Some(&self.linkability_flag),
+        )
+    }
+}
+
+prop_compose! {
+    fn signed()(
+        keys in prop::collection::vec(any::<[u8; 32]>(), 1..=8),
+        signer in any::<Index>(),
+        message in "\\PC{0,64}",
+        linkability_flag in "\\PC{0,16}",
+        seed in any::<[u8; 32]>(),
+    ) -> Signed {
+        let private_keys: Vec<Scalar> = keys.iter().map(|key| derive_scalar(key)).collect();
+        let ring: Vec<AffinePoint> = private_keys
+            .iter()
+            .map(|key| (ProjectivePoint::GENERATOR * key).to_affine())
+            .collect();
+        let responses: Vec<Scalar> = (0..ring.len())
+            .map(|i| derive_scalar(&[&seed[..], &i.to_be_bytes()].concat()))
+            .collect();
+        let signer_index = signer.index(ring.len());
+        let signature = sign_lsag_digest(
+            &ring,
+            &Sha256::digest(message.as_bytes()).into(),
+            signer_index,
+            private_keys[signer_index],
+            derive_scalar(&seed),
+            &responses,
+            Some(&linkability_flag),
+        )
+        .unwrap();
+        Signed { ring, message, linkability_flag, signature }
+    }
+}
+
+/// A corruption of a single element of a signature or of what it signs.
+#[derive(Debug, Clone)]
+enum Corruption {
+    C0,
+    Response(Index),
+    RingMember(Index),
+    KeyImage,
+    Message(u8),
+    LinkabilityFlag(char),
+}
+
+fn corruption() -> impl Strategy<Value = Corruption> {
+    prop_oneof![
+        Just(Corruption::C0),
+        any::<Index>().prop_map(Corruption::Response),
+        any::<Index>().prop_map(Corruption::RingMember),
+        Just(Corruption::KeyImage),
+        any::<u8>().prop_map(Corruption::Message),
+        any::<char>().prop_map(Corruption::LinkabilityFlag),
+    ]
+}
+
+fn corrupt(signed: &mut Signed, corruption: &Corruption) {
+    let g = ProjectivePoint::GENERATOR;
+    let ring_size = signed.ring.len();
+    let signature = &mut signed.signature;
+    match corruption {
+        Corruption::C0 => signature.c0 += Scalar::ONE,
+        Corruption::Response(index) => signature.responses[index.index(ring_size)] += Scalar::ONE,
+        Corruption::RingMember(index) => {
+            let member = &mut signed.ring[index.index(ring_size)];
+            *member = (g + *member).to_affine();
+        }
+        Corruption::KeyImage => signature.key_image = (g + signature.key_image).to_affine(),
+        Corruption::Message(byte) => signed.message.push(*byte as char),
+        Corruption::LinkabilityFlag(c) => signed.linkability_flag.push(*c),
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn signatures_verify_natively(signed in signed()) {
+        prop_assert!(signed.verify());
+    }
+
+    #[test]
+    fn corrupted_signatures_are_rejected(mut signed in signed(), corruption in corruption()) {
+        corrupt(&mut signed, &corruption);
+        prop_assert!(!signed.verify());
+    }
+}
+
+proptest! {
+    // each case executes the guest
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn guest_journal_matches_the_host_digest(signed in signed()) {
+        // RISC0_SKIP_BUILD leaves the guest unbuilt
+        if LSAG_VERIFIER_ELF.is_empty() {
+            return Ok(());
+        }
+        let input = encode_b64_lsag(
+            &signed.message,
+            &signed.ring,
+            &signed.signature,
+            &signed.linkability_flag,
+        )
+        .unwrap();
+        let env = ExecutorEnv::builder().write(&input).unwrap().build().unwrap();
+        let session = default_executor().execute(env, LSAG_VERIFIER_ELF).unwrap();
+
+        let expected = to_minimal_lsag_digest(
+            &signed.ring,
+            signed.message.as_bytes(),
+            signed.signature.key_image,
+            Some(&signed.linkability_flag),
+        );
+        prop_assert_eq!(session.journal.bytes, expected.to_vec());
+    }
+}
@@ -64,10 +64,12 @@ contract LsagVerifierDeploy is Script, RiscZeroCheats {
         if (bytes(configProfile).length != 0) {
             console2.log("Deploying using config profile:", configProfile);
             string memory configProfileKey = string.concat(".profile.", configProfile);
-            address riscZeroVerifierAddress =
-                stdToml.readAddress(config, string.concat(configProfileKey, ".riscZeroVerifierAddress"));
             // If set, use the predeployed verifier address found in the config.
-            verifier = IRiscZeroVerifier(riscZeroVerifierAddress);
+            try vm.parseTomlAddress(config, string.concat(configProfileKey, ".riscZeroVerifierAddress")) returns (
+                address riscZeroVerifierAddress
+            ) {
+                verifier = IRiscZeroVerifier(riscZeroVerifierAddress);
+            } catch {}
         }
 
         // Determine the wallet to send transactions from.